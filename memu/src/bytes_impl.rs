@@ -496,6 +496,28 @@ macro_rules! data_impl {
             }
         }
 
+        impl std::str::FromStr for $self {
+            type Err = $crate::parse::ParseUnitError;
+
+            /// Parses an amount with an optional unit suffix such as `KB` or `GB`.
+            /// Suffixes are case insensitive, a number without a suffix is an amount of this unit.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("# use memu::units::", stringify!($self), ";")]
+            #[doc = concat!("let unit: ", stringify!($self), " = \"2\".parse().unwrap();")]
+            ///
+            #[doc = concat!("assert_eq!(unit, ", stringify!($self), "::from_u8(2));")]
+            ///
+            #[doc = concat!("let unit: ", stringify!($self), " = \"512B\".parse().unwrap();")]
+            ///
+            /// assert_eq!(*unit, 512);
+            /// ```
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $crate::parse::parse_bytes(s, Self::FACTOR).map(<$self>::new)
+            }
+        }
+
         impl std::ops::Deref for $self {
             type Target = u64;
 
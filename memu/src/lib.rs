@@ -9,6 +9,9 @@ pub mod units;
 /// Memory constants for conversion between units.
 pub mod constants;
 
+/// Parsing units of data from strings.
+pub mod parse;
+
 /// Macros for easy creation of memory units.
 #[doc(hidden)]
 #[cfg(feature = "macro")]
@@ -0,0 +1,78 @@
+use std::{error::Error, fmt::Display};
+
+use crate::constants::{BYTE, GIGABYTE, KILOBYTE, MEGABYTE, PETABYTE, TERABYTE};
+
+/// An error returned when parsing a unit of data from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseUnitError {
+	/// The string contains no number.
+	Empty,
+	/// The number could not be parsed.
+	InvalidNumber(String),
+	/// The unit suffix is unknown.
+	UnknownUnit(String),
+	/// The amount does not fit into a [`u64`] of bytes.
+	Overflow,
+}
+
+impl Display for ParseUnitError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Empty => write!(f, "cannot parse a unit from an empty string"),
+			Self::InvalidNumber(number) => write!(f, "invalid number `{}`", number),
+			Self::UnknownUnit(unit) => write!(
+				f,
+				"unknown unit `{}`, expected one of B, KB, MB, GB, TB or PB",
+				unit
+			),
+			Self::Overflow => write!(f, "amount is too large"),
+		}
+	}
+}
+
+impl Error for ParseUnitError {}
+
+/// Parses a string like `"1.5MB"` into an amount of bytes.
+///
+/// A number without a unit suffix is multiplied by `default_factor`.
+pub(crate) fn parse_bytes(s: &str, default_factor: u64) -> Result<u64, ParseUnitError> {
+	let s = s.trim();
+	let split = s
+		.find(|c: char| !(c.is_ascii_digit() || c == '.'))
+		.unwrap_or(s.len());
+	let (number, unit) = s.split_at(split);
+
+	if number.is_empty() {
+		return Err(if s.is_empty() {
+			ParseUnitError::Empty
+		} else {
+			ParseUnitError::InvalidNumber(s.to_owned())
+		});
+	}
+
+	let factor = match unit.trim().to_ascii_uppercase().as_str() {
+		"" => default_factor,
+		"B" => BYTE,
+		"K" | "KB" | "KIB" => KILOBYTE,
+		"M" | "MB" | "MIB" => MEGABYTE,
+		"G" | "GB" | "GIB" => GIGABYTE,
+		"T" | "TB" | "TIB" => TERABYTE,
+		"P" | "PB" | "PIB" => PETABYTE,
+		_ => return Err(ParseUnitError::UnknownUnit(unit.trim().to_owned())),
+	};
+
+	if let Ok(number) = number.parse::<u64>() {
+		return number.checked_mul(factor).ok_or(ParseUnitError::Overflow);
+	}
+
+	let number: f64 = number
+		.parse()
+		.map_err(|_| ParseUnitError::InvalidNumber(number.to_owned()))?;
+	let bytes = number * factor as f64;
+
+	if bytes > u64::MAX as f64 {
+		Err(ParseUnitError::Overflow)
+	} else {
+		Ok(bytes as u64)
+	}
+}
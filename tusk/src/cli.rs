@@ -0,0 +1,99 @@
+use std::{env, path::PathBuf, time::Duration};
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::{
+	config::{datapoints::History, parse::parse_duration, Config},
+	terminal::Screen,
+};
+
+/// Terminal based cpu profiler for linux.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Args {
+	/// Path to the configuration file.
+	///
+	/// Takes precedence over `$XDG_CONFIG_HOME/tusk/config.yaml` and
+	/// `$HOME/.config/tusk/config.yaml`.
+	#[arg(short, long, value_name = "FILE", global = true)]
+	pub config: Option<PathBuf>,
+	/// The theme to use, either a built-in theme or the path to a theme file.
+	#[arg(long, value_name = "THEME", global = true)]
+	pub theme: Option<String>,
+	/// How long a tick should be, the terminal is drawn once every tick. For example `250ms` or `1s`.
+	#[arg(short, long, value_name = "DURATION", value_parser = parse_duration, global = true)]
+	pub tick: Option<Duration>,
+	/// The time between two samples, for example `250ms` or `1s`.
+	#[arg(short, long, value_name = "DURATION", value_parser = parse_duration, global = true)]
+	pub interval: Option<Duration>,
+	/// How many datapoints every graph keeps.
+	#[arg(long, value_name = "DATAPOINTS", value_parser = clap::value_parser!(u16).range(2..), global = true)]
+	pub history: Option<u16>,
+	/// Draw without colors, also enabled by setting `NO_COLOR`.
+	#[arg(long, global = true)]
+	pub no_color: bool,
+	/// Start tracking the process with this pid.
+	#[arg(short, long, global = true, conflicts_with = "name")]
+	pub pid: Option<u32>,
+	/// Start tracking the first process whose name contains this pattern.
+	#[arg(short, long, value_name = "PATTERN", global = true)]
+	pub name: Option<String>,
+	/// The tab shown on start: default, processes, disks, tracked, debug or custom.
+	#[arg(long, value_name = "TAB", global = true)]
+	pub tab: Option<Screen>,
+	#[command(subcommand)]
+	pub command: Option<Command>,
+}
+
+/// Non-interactive modes, printing to stdout instead of drawing the terminal ui.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+	/// Print a single sample of all metrics and exit.
+	Snapshot {
+		/// The output format.
+		#[arg(short, long, value_enum, default_value_t = Format::Text)]
+		format: Format,
+	},
+	/// Print a sample every sampling interval as csv.
+	Record {
+		/// Stop after this many samples instead of running until interrupted.
+		#[arg(short, long, value_name = "COUNT")]
+		samples: Option<usize>,
+	},
+	/// List all collectors, whether they are enabled and the metrics they produce.
+	Collectors,
+}
+
+/// The output formats of [`Command::Snapshot`].
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+	/// Human readable text.
+	Text,
+	/// Yaml, for further processing.
+	Yaml,
+}
+
+impl Args {
+	/// Overrides the values of `config` with the ones given on the command line.
+	pub fn apply(&self, config: &mut Config) {
+		if let Some(theme) = &self.theme {
+			config.theme = Some(theme.clone());
+		}
+
+		if self.no_color || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+			config.no_color = true;
+		}
+
+		if let Some(tick) = self.tick {
+			config.tick = tick;
+		}
+
+		if let Some(interval) = self.interval {
+			config.sampling.interval = interval;
+		}
+
+		if let Some(history) = self.history {
+			config.history = History::all(history as usize);
+		}
+	}
+}
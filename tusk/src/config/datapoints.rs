@@ -0,0 +1,262 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use memu::units::KiloByte;
+use serde::{Deserialize, Serialize};
+
+use super::parse::{duration, unit};
+
+/// How often data is collected.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Sampling {
+	/// The time between two samples, each sample adds a datapoint to the graphs.
+	#[serde(with = "duration")]
+	pub interval: Duration,
+	/// The time between two samples of all processes.
+	#[serde(with = "duration")]
+	pub processes: Duration,
+}
+
+impl Default for Sampling {
+	fn default() -> Self {
+		Sampling {
+			interval: Duration::from_millis(200),
+			processes: Duration::from_secs(1),
+		}
+	}
+}
+
+/// Which collectors are enabled, by name.
+///
+/// Collectors missing from the map are enabled.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Collectors(pub BTreeMap<String, bool>);
+
+impl Collectors {
+	/// Checks whether the collector called `name` is enabled.
+	pub fn is_enabled(&self, name: &str) -> bool {
+		self.0.get(name).copied().unwrap_or(true)
+	}
+}
+
+/// Which network interfaces are sampled.
+///
+/// Names may end in `*` to match every interface starting with the rest, like `veth*`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Interfaces {
+	/// Only interfaces matching one of these are sampled, all if empty.
+	pub include: Vec<String>,
+	/// Interfaces matching one of these are never sampled.
+	pub exclude: Vec<String>,
+}
+
+impl Interfaces {
+	/// Checks whether the interface called `name` is sampled.
+	pub fn is_included(&self, name: &str) -> bool {
+		let matches = |pattern: &String| match pattern.strip_suffix('*') {
+			Some(prefix) => name.starts_with(prefix),
+			None => name == pattern,
+		};
+
+		(self.include.is_empty() || self.include.iter().any(matches))
+			&& !self.exclude.iter().any(matches)
+	}
+}
+
+impl Default for Interfaces {
+	/// Leaves out the loopback interface, its traffic never leaves the machine.
+	fn default() -> Self {
+		Interfaces {
+			include: Vec::new(),
+			exclude: vec![String::from("lo")],
+		}
+	}
+}
+
+/// How many datapoints are kept for each graph.
+///
+/// In the configuration file this is either a single number used for all graphs,
+/// or a map setting each graph separately.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(from = "HistoryRepr")]
+#[serde(rename_all = "camelCase")]
+pub struct History {
+	/// How many datapoints for cpu usage should be collected.
+	pub cpu_usage: usize,
+	/// How many datapoints for the frequency of each core should be collected.
+	pub cpu_frequency: usize,
+	/// How many datapoints for the cpu time breakdown should be collected, one per second.
+	pub cpu_times: usize,
+	/// How many datapoints for the memory should be collected.
+	pub memory: usize,
+	/// How many datapoints for the disk throughput should be collected.
+	pub disk: usize,
+	/// How many datapoints for the network should be collected.
+	pub network: usize,
+	/// How many datapoints for the load averages should be collected, one per second.
+	pub load: usize,
+	/// How many datapoints for every temperature sensor should be collected.
+	pub sensors: usize,
+	/// How many datapoints for the pressure should be collected, one every two seconds.
+	pub pressure: usize,
+	/// How many datapoints should be collected on the tracked process.
+	pub tracked_process: usize,
+	/// How many ticks should be tracked in debug mode.
+	pub debug_ticks: usize,
+}
+
+impl History {
+	/// Uses the same amount of datapoints for every graph.
+	pub const fn all(datapoints: usize) -> Self {
+		History {
+			cpu_usage: datapoints,
+			cpu_frequency: datapoints,
+			cpu_times: datapoints,
+			memory: datapoints,
+			disk: datapoints,
+			network: datapoints,
+			load: datapoints,
+			sensors: datapoints,
+			pressure: datapoints,
+			tracked_process: datapoints,
+			debug_ticks: datapoints,
+		}
+	}
+
+	/// Returns the name and value of every history length.
+	pub const fn entries(&self) -> [(&'static str, usize); 11] {
+		[
+			("history.cpuUsage", self.cpu_usage),
+			("history.cpuFrequency", self.cpu_frequency),
+			("history.cpuTimes", self.cpu_times),
+			("history.memory", self.memory),
+			("history.disk", self.disk),
+			("history.network", self.network),
+			("history.load", self.load),
+			("history.sensors", self.sensors),
+			("history.pressure", self.pressure),
+			("history.trackedProcess", self.tracked_process),
+			("history.debugTicks", self.debug_ticks),
+		]
+	}
+}
+
+impl Default for History {
+	fn default() -> Self {
+		Self::all(100)
+	}
+}
+
+/// The two ways of writing the history in the configuration file.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HistoryRepr {
+	All(usize),
+	Each(HistoryEach),
+}
+
+#[derive(Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct HistoryEach {
+	cpu_usage: usize,
+	cpu_frequency: usize,
+	cpu_times: usize,
+	memory: usize,
+	disk: usize,
+	network: usize,
+	load: usize,
+	sensors: usize,
+	pressure: usize,
+	tracked_process: usize,
+	debug_ticks: usize,
+}
+
+impl Default for HistoryEach {
+	fn default() -> Self {
+		let History {
+			cpu_usage,
+			cpu_frequency,
+			cpu_times,
+			memory,
+			disk,
+			network,
+			load,
+			sensors,
+			pressure,
+			tracked_process,
+			debug_ticks,
+		} = History::default();
+
+		HistoryEach {
+			cpu_usage,
+			cpu_frequency,
+			cpu_times,
+			memory,
+			disk,
+			network,
+			load,
+			sensors,
+			pressure,
+			tracked_process,
+			debug_ticks,
+		}
+	}
+}
+
+impl From<HistoryRepr> for History {
+	fn from(value: HistoryRepr) -> Self {
+		match value {
+			HistoryRepr::All(datapoints) => History::all(datapoints),
+			HistoryRepr::Each(each) => History {
+				cpu_usage: each.cpu_usage,
+				cpu_frequency: each.cpu_frequency,
+				cpu_times: each.cpu_times,
+				memory: each.memory,
+				disk: each.disk,
+				network: each.network,
+				load: each.load,
+				sensors: each.sensors,
+				pressure: each.pressure,
+				tracked_process: each.tracked_process,
+				debug_ticks: each.debug_ticks,
+			},
+		}
+	}
+}
+
+/// The unit network traffic is shown in.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RateUnit {
+	/// Bytes per second, from B/s to TB/s in steps of 1024.
+	#[default]
+	Bytes,
+	/// Bits per second, from b/s to Tb/s in steps of 1000 like network speeds are advertised.
+	Bits,
+}
+
+/// Controls the network graphs.
+///
+/// Both graphs scale to the highest amount of traffic in either direction and pick the unit
+/// fitting it best.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NetworkGraphs {
+	/// The unit the graphs and the interface table are labelled in.
+	pub unit: RateUnit,
+	/// The amount displayed at minimum, so a bit of traffic on an idle network doesn't fill the
+	/// graphs.
+	#[serde(with = "unit")]
+	pub min: KiloByte,
+}
+
+impl Default for NetworkGraphs {
+	fn default() -> Self {
+		NetworkGraphs {
+			unit: RateUnit::default(),
+			min: KiloByte::from_u8(1),
+		}
+	}
+}
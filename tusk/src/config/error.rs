@@ -0,0 +1,98 @@
+use std::{
+	error::Error,
+	fmt::Display,
+	io,
+	path::{Path, PathBuf},
+};
+
+use super::theme::BUILTIN_THEMES;
+
+/// An error that occured while loading a configuration file.
+#[derive(Debug)]
+pub enum ConfigError {
+	/// The file could not be read.
+	Io { path: PathBuf, error: io::Error },
+	/// The file contents are not valid.
+	Parse {
+		path: PathBuf,
+		line: usize,
+		column: usize,
+		message: String,
+	},
+	/// A value is out of its valid range.
+	Invalid {
+		path: Option<PathBuf>,
+		message: String,
+	},
+	/// The theme is neither a built-in theme nor a theme file.
+	UnknownTheme(String),
+}
+
+impl ConfigError {
+	/// Creates an error for a file that could not be read.
+	pub fn io(path: &Path, error: io::Error) -> Self {
+		ConfigError::Io {
+			path: path.to_owned(),
+			error,
+		}
+	}
+
+	/// Creates an error for a file that could not be parsed.
+	pub fn parse(path: &Path, error: serde_yaml::Error) -> Self {
+		let (line, column) = error
+			.location()
+			.map(|location| (location.line(), location.column()))
+			.unwrap_or((0, 0));
+
+		// The location is already part of the error, we keep it separate.
+		let message = error.to_string();
+		let message = match message.split_once(" at line ") {
+			Some((message, _)) => message.to_owned(),
+			None => message,
+		};
+
+		ConfigError::Parse {
+			path: path.to_owned(),
+			line,
+			column,
+			message,
+		}
+	}
+}
+
+impl Display for ConfigError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+			Self::Parse {
+				path,
+				line,
+				column,
+				message,
+			} => write!(f, "{}:{}:{}: {}", path.display(), line, column, message),
+			Self::Invalid {
+				path: Some(path),
+				message,
+			} => write!(f, "{}: {}", path.display(), message),
+			Self::Invalid {
+				path: None,
+				message,
+			} => write!(f, "{}", message),
+			Self::UnknownTheme(name) => write!(
+				f,
+				"unknown theme `{}`, expected a theme file or one of {}",
+				name,
+				BUILTIN_THEMES.join(", ")
+			),
+		}
+	}
+}
+
+impl Error for ConfigError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Io { error, .. } => Some(error),
+			Self::Parse { .. } | Self::Invalid { .. } | Self::UnknownTheme(_) => None,
+		}
+	}
+}
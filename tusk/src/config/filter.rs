@@ -0,0 +1,658 @@
+use std::{fmt::Display, str::FromStr};
+
+use memu::units::MegaByte;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::parse::parse_duration;
+use crate::terminal::Process;
+
+/// Decides which processes are listed, parsed from a query like `cpu > 5 && mem > 100MB`.
+///
+/// A query is made of terms joined by `&&` and `||`, negated with `!` and grouped with
+/// parentheses. Terms next to each other must all match. A term is either
+/// - a comparison `field op value`, the fields are `pid`, `name`, `user`, `cpu` in percent,
+///   `mem`, `read` and `written` in megabytes or with a unit like `1.5GB` and `time` in seconds
+///   or with a unit like `2h`. Numbers are compared with `==`, `!=`, `>`, `>=`, `<` and `<=`,
+///   text with `==`, `!=` and `~`, which matches a regex.
+/// - a word, matching processes whose name or pid contains it.
+/// - a regex between slashes like `/^k.*d$/`, matching processes whose name matches it.
+///
+/// Text containing spaces or operators can be quoted with `"`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(try_from = "String", into = "String")]
+pub struct Filter {
+	/// The query this filter was parsed from.
+	query: String,
+	expression: Expression,
+}
+
+impl Filter {
+	/// Whether `process` passes this filter.
+	pub fn matches(&self, process: &Process) -> bool {
+		self.expression.matches(process)
+	}
+
+	/// Combines this filter and `other`, letting only processes passing both through.
+	pub fn and(&self, other: &Filter) -> Filter {
+		Filter {
+			query: format!("({}) && ({})", self.query, other.query),
+			expression: Expression::And(
+				Box::new(self.expression.clone()),
+				Box::new(other.expression.clone()),
+			),
+		}
+	}
+}
+
+impl FromStr for Filter {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let tokens = tokenize(s)?;
+		let mut parser = Parser {
+			tokens: &tokens,
+			position: 0,
+		};
+		let expression = parser.or()?;
+		if let Some(token) = parser.peek() {
+			return Err(format!("unexpected `{}`", token));
+		}
+
+		Ok(Filter {
+			query: s.trim().to_owned(),
+			expression,
+		})
+	}
+}
+
+impl TryFrom<String> for Filter {
+	type Error = String;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		value.parse()
+	}
+}
+
+impl From<Filter> for String {
+	fn from(filter: Filter) -> Self {
+		filter.query
+	}
+}
+
+impl Display for Filter {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.query)
+	}
+}
+
+/// Filters are equal if they were parsed from the same query.
+impl PartialEq for Filter {
+	fn eq(&self, other: &Self) -> bool {
+		self.query == other.query
+	}
+}
+
+impl Eq for Filter {}
+
+#[derive(Clone, Debug)]
+enum Expression {
+	And(Box<Expression>, Box<Expression>),
+	Or(Box<Expression>, Box<Expression>),
+	Not(Box<Expression>),
+	/// Matches the name or pid containing the text.
+	Contains(String, Option<Digits>),
+	/// Matches the name matching the regex.
+	Regex(Regex),
+	Compare(Comparison),
+}
+
+impl Expression {
+	fn matches(&self, process: &Process) -> bool {
+		match self {
+			Self::And(left, right) => left.matches(process) && right.matches(process),
+			Self::Or(left, right) => left.matches(process) || right.matches(process),
+			Self::Not(expression) => !expression.matches(process),
+			Self::Contains(text, digits) => {
+				process.name.contains(text.as_str())
+					|| digits.is_some_and(|digits| digits.found_in(process.pid))
+			},
+			Self::Regex(regex) => regex.is_match(&process.name),
+			Self::Compare(comparison) => comparison.matches(process),
+		}
+	}
+}
+
+/// A word made only of digits, looked for in pids without formatting them.
+#[derive(Clone, Copy, Debug)]
+struct Digits {
+	value: u64,
+	/// 10 to the power of the number of digits.
+	modulus: u64,
+}
+
+impl Digits {
+	/// Gets the digits of `word`, if it is a number short enough to be found in a pid.
+	fn parse(word: &str) -> Option<Self> {
+		if word.is_empty() || word.len() > 10 || !word.bytes().all(|b| b.is_ascii_digit()) {
+			return None;
+		}
+
+		Some(Digits {
+			value: word.parse().ok()?,
+			modulus: 10u64.pow(word.len() as u32),
+		})
+	}
+
+	/// Whether the decimal digits of `pid` contain these digits.
+	fn found_in(self, pid: u32) -> bool {
+		let mut rest = pid as u64;
+		loop {
+			// The lowest digits of `rest` only count if it has as many digits as the word,
+			// so `05` isn't found in `5`.
+			if rest % self.modulus == self.value
+				&& (rest >= self.modulus / 10 || self.modulus == 10)
+			{
+				return true;
+			}
+			if rest < self.modulus {
+				return false;
+			}
+			rest /= 10;
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Field {
+	Pid,
+	Name,
+	User,
+	Cpu,
+	Memory,
+	Time,
+	Written,
+	Read,
+}
+
+impl Field {
+	fn parse(s: &str) -> Option<Self> {
+		match s.to_ascii_lowercase().as_str() {
+			"pid" => Some(Self::Pid),
+			"name" => Some(Self::Name),
+			"user" => Some(Self::User),
+			"cpu" => Some(Self::Cpu),
+			"mem" | "memory" => Some(Self::Memory),
+			"time" => Some(Self::Time),
+			"written" | "write" => Some(Self::Written),
+			"read" => Some(Self::Read),
+			_ => None,
+		}
+	}
+
+	fn name(self) -> &'static str {
+		match self {
+			Self::Pid => "pid",
+			Self::Name => "name",
+			Self::User => "user",
+			Self::Cpu => "cpu",
+			Self::Memory => "mem",
+			Self::Time => "time",
+			Self::Written => "written",
+			Self::Read => "read",
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Operator {
+	Equal,
+	NotEqual,
+	Greater,
+	GreaterEqual,
+	Less,
+	LessEqual,
+	Matches,
+}
+
+impl Operator {
+	fn parse(s: &str) -> Option<Self> {
+		match s {
+			"==" | "=" => Some(Self::Equal),
+			"!=" => Some(Self::NotEqual),
+			">" => Some(Self::Greater),
+			">=" => Some(Self::GreaterEqual),
+			"<" => Some(Self::Less),
+			"<=" => Some(Self::LessEqual),
+			"~" => Some(Self::Matches),
+			_ => None,
+		}
+	}
+
+	/// Applies this operator to two values that can be ordered.
+	fn compare<T: PartialOrd>(self, left: T, right: T) -> bool {
+		match self {
+			Self::Equal => left == right,
+			Self::NotEqual => left != right,
+			Self::Greater => left > right,
+			Self::GreaterEqual => left >= right,
+			Self::Less => left < right,
+			Self::LessEqual => left <= right,
+			Self::Matches => false,
+		}
+	}
+}
+
+/// The value a field is compared with, parsed according to the field.
+#[derive(Clone, Debug)]
+enum Value {
+	Number(f64),
+	Bytes(MegaByte),
+	Text(String),
+	Regex(Regex),
+}
+
+#[derive(Clone, Debug)]
+struct Comparison {
+	field: Field,
+	operator: Operator,
+	value: Value,
+}
+
+impl Comparison {
+	fn new(field: Field, operator: Operator, value: &str) -> Result<Self, String> {
+		let value = match field {
+			Field::Name | Field::User => match operator {
+				Operator::Matches => Value::Regex(
+					Regex::new(value).map_err(|e| format!("invalid regex `{}`: {}", value, e))?,
+				),
+				Operator::Equal | Operator::NotEqual => Value::Text(value.to_owned()),
+				_ => {
+					return Err(format!(
+						"`{}` can only be compared with ==, != or ~",
+						field.name()
+					))
+				},
+			},
+			_ if operator == Operator::Matches => {
+				return Err(format!("`{}` can't be matched with ~", field.name()))
+			},
+			Field::Pid => Value::Number(
+				value
+					.parse::<u32>()
+					.map_err(|_| format!("invalid pid `{}`", value))? as f64,
+			),
+			Field::Cpu => Value::Number(
+				value
+					.trim_end_matches('%')
+					.parse()
+					.map_err(|_| format!("invalid cpu usage `{}`", value))?,
+			),
+			Field::Time => Value::Number(match value.parse::<f64>() {
+				Ok(seconds) => seconds,
+				Err(_) => parse_duration(value)?.as_secs_f64(),
+			}),
+			Field::Memory | Field::Written | Field::Read => Value::Bytes(
+				value
+					.parse()
+					.map_err(|e| format!("invalid amount `{}`: {}", value, e))?,
+			),
+		};
+
+		Ok(Comparison {
+			field,
+			operator,
+			value,
+		})
+	}
+
+	fn matches(&self, process: &Process) -> bool {
+		let operator = self.operator;
+		match (&self.value, self.field) {
+			(Value::Regex(regex), Field::Name) => regex.is_match(&process.name),
+			(Value::Regex(regex), Field::User) => regex.is_match(&process.user),
+			(Value::Text(text), Field::Name) => operator.compare(&process.name, text),
+			(Value::Text(text), Field::User) => operator.compare(&process.user, text),
+			(Value::Number(number), Field::Pid) => operator.compare(process.pid as f64, *number),
+			(Value::Number(number), Field::Cpu) => {
+				operator.compare(process.cpu_usage as f64, *number)
+			},
+			(Value::Number(number), Field::Time) => operator.compare(process.time as f64, *number),
+			(Value::Bytes(bytes), Field::Memory) => operator.compare(process.memory, *bytes),
+			(Value::Bytes(bytes), Field::Written) => {
+				operator.compare(process.total_written, *bytes)
+			},
+			(Value::Bytes(bytes), Field::Read) => operator.compare(process.total_read, *bytes),
+			_ => false,
+		}
+	}
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Token {
+	And,
+	Or,
+	Not,
+	Open,
+	Close,
+	Operator(String),
+	Word(String),
+	/// A regex written between slashes.
+	Regex(String),
+}
+
+impl Display for Token {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::And => write!(f, "&&"),
+			Self::Or => write!(f, "||"),
+			Self::Not => write!(f, "!"),
+			Self::Open => write!(f, "("),
+			Self::Close => write!(f, ")"),
+			Self::Operator(operator) => write!(f, "{}", operator),
+			Self::Word(word) => write!(f, "{}", word),
+			Self::Regex(regex) => write!(f, "/{}/", regex),
+		}
+	}
+}
+
+/// Characters that end a word.
+const SPECIAL: &[char] = &['&', '|', '!', '(', ')', '=', '<', '>', '~', '"'];
+
+/// Splits a query into tokens.
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+	let mut tokens = Vec::new();
+	let mut chars = s.chars().peekable();
+
+	while let Some(&ch) = chars.peek() {
+		if ch.is_whitespace() {
+			chars.next();
+			continue;
+		}
+
+		let token = match ch {
+			'&' | '|' => {
+				chars.next();
+				if chars.next_if_eq(&ch).is_none() {
+					return Err(format!("expected `{}{}`", ch, ch));
+				}
+				if ch == '&' {
+					Token::And
+				} else {
+					Token::Or
+				}
+			},
+			'(' => {
+				chars.next();
+				Token::Open
+			},
+			')' => {
+				chars.next();
+				Token::Close
+			},
+			'=' | '<' | '>' | '!' | '~' => {
+				chars.next();
+				match chars.next_if_eq(&'=') {
+					Some(_) => Token::Operator(format!("{}=", ch)),
+					None if ch == '!' => Token::Not,
+					None => Token::Operator(ch.to_string()),
+				}
+			},
+			'"' | '/' => {
+				chars.next();
+				let mut text = String::new();
+				loop {
+					match chars.next() {
+						Some(end) if end == ch => break,
+						// A backslash keeps the quote, regexes need it to keep its meaning.
+						Some('\\') if chars.peek() == Some(&ch) => {
+							if ch == '/' {
+								text.push('\\');
+							}
+							text.push(ch);
+							chars.next();
+						},
+						Some(other) => text.push(other),
+						None => return Err(format!("missing closing `{}`", ch)),
+					}
+				}
+				if ch == '"' {
+					Token::Word(text)
+				} else {
+					Token::Regex(text)
+				}
+			},
+			_ => {
+				let mut word = String::new();
+				while let Some(ch) =
+					chars.next_if(|ch| !ch.is_whitespace() && !SPECIAL.contains(ch))
+				{
+					word.push(ch);
+				}
+				Token::Word(word)
+			},
+		};
+		tokens.push(token);
+	}
+
+	Ok(tokens)
+}
+
+/// Parses tokens into an expression, `&&` binds stronger than `||`.
+struct Parser<'a> {
+	tokens: &'a [Token],
+	position: usize,
+}
+
+impl Parser<'_> {
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.position)
+	}
+
+	fn next(&mut self) -> Option<&Token> {
+		let token = self.tokens.get(self.position);
+		self.position += 1;
+		token
+	}
+
+	fn or(&mut self) -> Result<Expression, String> {
+		let mut expression = self.and()?;
+		while self.peek() == Some(&Token::Or) {
+			self.next();
+			expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+		}
+		Ok(expression)
+	}
+
+	/// Terms joined by `&&` or just written next to each other.
+	fn and(&mut self) -> Result<Expression, String> {
+		let mut expression = self.term()?;
+		loop {
+			match self.peek() {
+				None | Some(Token::Or) | Some(Token::Close) => return Ok(expression),
+				Some(Token::And) => {
+					self.next();
+				},
+				Some(_) => (),
+			}
+			expression = Expression::And(Box::new(expression), Box::new(self.term()?));
+		}
+	}
+
+	fn term(&mut self) -> Result<Expression, String> {
+		match self.next().cloned() {
+			Some(Token::Not) => Ok(Expression::Not(Box::new(self.term()?))),
+			Some(Token::Open) => {
+				let expression = self.or()?;
+				match self.next() {
+					Some(Token::Close) => Ok(expression),
+					_ => Err(String::from("missing closing `)`")),
+				}
+			},
+			Some(Token::Regex(regex)) => {
+				Ok(Expression::Regex(Regex::new(&regex).map_err(|e| {
+					format!("invalid regex `{}`: {}", regex, e)
+				})?))
+			},
+			Some(Token::Word(word)) => match self.peek() {
+				Some(Token::Operator(operator)) => {
+					let field = Field::parse(&word).ok_or_else(|| {
+						format!(
+							"unknown field `{}`, expected one of pid, name, user, cpu, mem, time, written or read",
+							word
+						)
+					})?;
+					let operator = Operator::parse(operator)
+						.ok_or_else(|| format!("unknown operator `{}`", operator))?;
+					self.next();
+					match self.next() {
+						Some(Token::Word(value)) => Ok(Expression::Compare(Comparison::new(
+							field, operator, value,
+						)?)),
+						Some(Token::Regex(value)) if operator == Operator::Matches => Ok(
+							Expression::Compare(Comparison::new(field, operator, value)?),
+						),
+						_ => Err(format!("expected a value after `{}`", word)),
+					}
+				},
+				_ => {
+					let digits = Digits::parse(&word);
+					Ok(Expression::Contains(word, digits))
+				},
+			},
+			Some(token) => Err(format!("unexpected `{}`", token)),
+			None => Err(String::from("expected a name, pid or comparison")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sysinfo::ProcessStatus;
+
+	use super::*;
+
+	fn process(pid: u32, name: &str, user: &str, cpu_usage: f32, memory: u64) -> Process {
+		Process {
+			pid,
+			parent: None,
+			name: name.to_owned(),
+			user: user.to_owned(),
+			time: 90,
+			memory: MegaByte::from(memory),
+			status: ProcessStatus::Run,
+			total_written: MegaByte::from(0u64),
+			total_read: MegaByte::from(0u64),
+			cpu_usage,
+		}
+	}
+
+	/// Gets the names of the processes matching `query`.
+	fn matching(query: &str) -> Vec<&'static str> {
+		let filter: Filter = query.parse().unwrap();
+		[
+			process(1, "systemd", "root", 0.5, 12),
+			process(420, "bash", "alice", 2.0, 5),
+			process(1050, "firefox", "alice", 30.0, 2048),
+			process(7, "kworker/0:1", "root", 0.0, 0),
+		]
+		.iter()
+		.filter(|process| filter.matches(process))
+		.map(|process| match process.pid {
+			1 => "systemd",
+			420 => "bash",
+			1050 => "firefox",
+			_ => "kworker",
+		})
+		.collect()
+	}
+
+	#[test]
+	fn precedence() {
+		// && binds tighter than ||.
+		assert_eq!(
+			matching("user == root || cpu > 1 && mem > 1GB"),
+			["systemd", "firefox", "kworker"]
+		);
+		assert_eq!(
+			matching("(user == root || cpu > 1) && mem > 1GB"),
+			["firefox"]
+		);
+		assert_eq!(matching("!user == root && !bash"), ["firefox"]);
+		assert_eq!(matching("!(user == root || bash)"), ["firefox"]);
+	}
+
+	#[test]
+	fn implicit_and() {
+		assert_eq!(matching("user == alice cpu > 10"), ["firefox"]);
+		assert_eq!(matching("fire fox"), ["firefox"]);
+		assert_eq!(
+			matching("user == alice bash || systemd"),
+			["systemd", "bash"]
+		);
+	}
+
+	#[test]
+	fn words_match_names_and_pids() {
+		assert_eq!(matching("sh"), ["bash"]);
+		assert_eq!(matching("42"), ["bash"]);
+		assert_eq!(matching("05"), ["firefox"]);
+		assert_eq!(matching("0"), ["bash", "firefox", "kworker"]);
+		assert_eq!(matching("007"), Vec::<&str>::new());
+	}
+
+	#[test]
+	fn quotes_and_regexes() {
+		assert_eq!(matching("\"kworker/0\""), ["kworker"]);
+		assert_eq!(matching("name == \"b\\\"ash\""), Vec::<&str>::new());
+		assert_eq!(matching("/^(bash|systemd)$/"), ["systemd", "bash"]);
+		assert_eq!(matching("/worker\\/0/"), ["kworker"]);
+		assert_eq!(matching("user ~ /^al/ && name ~ fox"), ["firefox"]);
+		assert_eq!(matching("name == \"a && b\""), Vec::<&str>::new());
+	}
+
+	#[test]
+	fn units() {
+		assert_eq!(matching("mem >= 2GB"), ["firefox"]);
+		assert_eq!(matching("mem > 1.5GB"), ["firefox"]);
+		assert_eq!(matching("mem > 10"), ["systemd", "firefox"]);
+		assert_eq!(matching("mem <= 5MB"), ["bash", "kworker"]);
+		assert_eq!(matching("cpu >= 2%"), ["bash", "firefox"]);
+		assert_eq!(
+			matching("time >= 1m"),
+			["systemd", "bash", "firefox", "kworker"]
+		);
+		assert_eq!(matching("time > 2m"), Vec::<&str>::new());
+	}
+
+	#[test]
+	fn errors() {
+		let error = |query: &str| query.parse::<Filter>().unwrap_err();
+
+		assert_eq!(error("(cpu > 1"), "missing closing `)`");
+		assert_eq!(error("cpu > 1)"), "unexpected `)`");
+		assert_eq!(error("()"), "unexpected `)`");
+		assert_eq!(
+			error("size > 1"),
+			"unknown field `size`, expected one of pid, name, user, cpu, mem, time, written or read"
+		);
+		assert_eq!(error("cpu >"), "expected a value after `cpu`");
+		assert_eq!(error("bash &"), "expected `&&`");
+		assert_eq!(error("\"bash"), "missing closing `\"`");
+		assert_eq!(error("/bash"), "missing closing `/`");
+		assert_eq!(
+			error("name > a"),
+			"`name` can only be compared with ==, != or ~"
+		);
+		assert_eq!(error("cpu ~ 1"), "`cpu` can't be matched with ~");
+		assert_eq!(error(""), "expected a name, pid or comparison");
+		assert!(error("mem > lots").starts_with("invalid amount `lots`"));
+	}
+
+	#[test]
+	fn query_round_trips() {
+		let filter: Filter = " cpu > 5 && mem > 100MB ".parse().unwrap();
+		assert_eq!(filter.to_string(), "cpu > 5 && mem > 100MB");
+		assert_eq!(String::from(filter.clone()).parse::<Filter>(), Ok(filter));
+	}
+}
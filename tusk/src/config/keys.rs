@@ -0,0 +1,699 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+/// Something the user can do by pressing keys.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Action {
+	/// Quits the application.
+	Quit,
+	/// Reloads the application.
+	Reload,
+	/// Switches to the next tab.
+	NextTab,
+	/// Switches to the previous tab.
+	PreviousTab,
+	/// Opens the input to enter the pid of the process to track.
+	StartTrackInput,
+	/// Opens the input to search the processes.
+	StartSearch,
+	/// Opens the input to save the current search as a view.
+	SaveView,
+	/// Opens the dialog picking a signal to send to the selected process.
+	SendSignal,
+	/// Opens the input to change the nice value of the selected process.
+	Renice,
+	/// Opens the input to change the io priority of the selected process.
+	SetIoPriority,
+	/// Opens the input to change the cores the selected process may run on.
+	SetAffinity,
+	/// Shows or hides the debug tab.
+	ToggleDebug,
+	/// Switches between the average cpu usage, a graph per core and a heatmap.
+	ToggleCpuView,
+	/// Selects the process above the selected one.
+	SelectUp,
+	/// Selects the process below the selected one.
+	SelectDown,
+	/// Moves the process selection up by a page.
+	SelectPageUp,
+	/// Moves the process selection down by a page.
+	SelectPageDown,
+	/// Selects the first process.
+	SelectFirst,
+	/// Selects the last process.
+	SelectLast,
+	/// Sorts the processes by the next column.
+	SortNext,
+	/// Sorts the processes by the previous column.
+	SortPrevious,
+	/// Switches between sorting the processes ascending and descending.
+	ToggleSortOrder,
+	/// Switches between listing the processes flat and as a tree.
+	ToggleProcessTree,
+	/// Hides or shows the children of the selected process in the tree.
+	ToggleCollapsed,
+	/// Selects the parent of the selected process.
+	SelectParent,
+	/// Dismisses the message in the status line.
+	ClearStatus,
+	/// Enters the typed input.
+	Confirm,
+	/// Closes the input without entering it.
+	Cancel,
+	/// Deletes the character before the cursor.
+	DeleteBackward,
+	/// Moves the cursor one character to the left.
+	CursorLeft,
+	/// Moves the cursor one character to the right.
+	CursorRight,
+	/// Moves the cursor to the start of the input.
+	CursorStart,
+	/// Moves the cursor to the end of the input.
+	CursorEnd,
+}
+
+/// The keymaps used depending on what the user is doing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+	/// Navigating the tabs.
+	Normal,
+	/// Typing into the input, characters without a binding are typed.
+	Input,
+	/// Answering a dialog.
+	Dialog,
+}
+
+/// A single key press, including its modifiers.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Key {
+	pub code: KeyCode,
+	pub modifiers: KeyModifiers,
+}
+
+impl Key {
+	/// Creates a key without modifiers.
+	pub const fn new(code: KeyCode) -> Self {
+		Key {
+			code,
+			modifiers: KeyModifiers::NONE,
+		}
+	}
+
+	/// The character typed by this key, if it is a plain character.
+	pub fn char(&self) -> Option<char> {
+		match self.code {
+			KeyCode::Char(ch) if self.modifiers.is_empty() => Some(ch),
+			_ => None,
+		}
+	}
+}
+
+impl From<KeyEvent> for Key {
+	/// Drops shift from characters and `BackTab`, since it is already part of the key code.
+	fn from(event: KeyEvent) -> Self {
+		let modifiers = match event.code {
+			KeyCode::Char(_) | KeyCode::BackTab => event.modifiers - KeyModifiers::SHIFT,
+			_ => event.modifiers,
+		};
+
+		Key {
+			code: event.code,
+			modifiers: modifiers
+				& (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT),
+		}
+	}
+}
+
+impl FromStr for Key {
+	type Err = String;
+
+	/// Parses a key like `q`, `G`, `ctrl+c`, `shift+tab` or `f1`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (modifiers, name) = match s.strip_suffix('+') {
+			Some(rest) if rest.is_empty() || rest.ends_with('+') => (rest, "+"),
+			_ => match s.rsplit_once('+') {
+				Some((modifiers, name)) => (modifiers, name),
+				None => ("", s),
+			},
+		};
+
+		let mut key = Key::new(KeyCode::Null);
+
+		for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+			key.modifiers |= match modifier.to_ascii_lowercase().as_str() {
+				"ctrl" | "control" => KeyModifiers::CONTROL,
+				"alt" | "meta" => KeyModifiers::ALT,
+				"shift" => KeyModifiers::SHIFT,
+				_ => {
+					return Err(format!(
+						"unknown modifier `{}` in `{}`, expected ctrl, alt or shift",
+						modifier, s
+					))
+				},
+			};
+		}
+
+		let mut chars = name.chars();
+		key.code = match (chars.next(), chars.next()) {
+			(Some(ch), None) => KeyCode::Char(ch),
+			_ => match name.to_ascii_lowercase().as_str() {
+				"esc" | "escape" => KeyCode::Esc,
+				"enter" | "return" => KeyCode::Enter,
+				"tab" => KeyCode::Tab,
+				"backtab" => KeyCode::BackTab,
+				"backspace" => KeyCode::Backspace,
+				"space" => KeyCode::Char(' '),
+				"delete" | "del" => KeyCode::Delete,
+				"insert" | "ins" => KeyCode::Insert,
+				"home" => KeyCode::Home,
+				"end" => KeyCode::End,
+				"pageup" | "pgup" => KeyCode::PageUp,
+				"pagedown" | "pgdn" => KeyCode::PageDown,
+				"up" => KeyCode::Up,
+				"down" => KeyCode::Down,
+				"left" => KeyCode::Left,
+				"right" => KeyCode::Right,
+				lower => match lower.strip_prefix('f').and_then(|n| n.parse().ok()) {
+					Some(n @ 1..=24) => KeyCode::F(n),
+					_ => return Err(format!("unknown key `{}`", name)),
+				},
+			},
+		};
+
+		// Shift is part of the key code for characters and `BackTab`, like in `Key::from`.
+		if key.modifiers.contains(KeyModifiers::SHIFT) {
+			match key.code {
+				KeyCode::Char(ch) => {
+					key.code = KeyCode::Char(ch.to_ascii_uppercase());
+					key.modifiers -= KeyModifiers::SHIFT;
+				},
+				KeyCode::Tab => {
+					key.code = KeyCode::BackTab;
+					key.modifiers -= KeyModifiers::SHIFT;
+				},
+				_ => (),
+			}
+		}
+
+		Ok(key)
+	}
+}
+
+impl Display for Key {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for (modifier, name) in [
+			(KeyModifiers::CONTROL, "ctrl+"),
+			(KeyModifiers::ALT, "alt+"),
+			(KeyModifiers::SHIFT, "shift+"),
+		] {
+			if self.modifiers.contains(modifier) {
+				write!(f, "{}", name)?;
+			}
+		}
+
+		match self.code {
+			KeyCode::Char(' ') => write!(f, "space"),
+			KeyCode::Char(ch) => write!(f, "{}", ch),
+			KeyCode::F(n) => write!(f, "f{}", n),
+			KeyCode::BackTab => write!(f, "shift+tab"),
+			code => write!(f, "{}", format!("{:?}", code).to_ascii_lowercase()),
+		}
+	}
+}
+
+/// One or more keys pressed after each other, written like `g t`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KeySequence(pub Vec<Key>);
+
+impl FromStr for KeySequence {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let keys = s
+			.split_whitespace()
+			.map(Key::from_str)
+			.collect::<Result<Vec<_>, _>>()?;
+
+		if keys.is_empty() {
+			return Err(String::from("a key binding can't be empty"));
+		}
+
+		Ok(KeySequence(keys))
+	}
+}
+
+impl Display for KeySequence {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for (i, key) in self.0.iter().enumerate() {
+			if i != 0 {
+				write!(f, " ")?;
+			}
+			write!(f, "{}", key)?;
+		}
+		Ok(())
+	}
+}
+
+/// A key sequence triggering an action.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Binding {
+	pub keys: KeySequence,
+	pub action: Action,
+}
+
+/// The result of looking up the keys pressed so far.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lookup {
+	/// The keys trigger this action.
+	Action(Action),
+	/// The keys are the start of at least one binding, more keys are needed.
+	Pending,
+	/// No binding starts with the keys.
+	None,
+}
+
+/// The built-in sets of bindings.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Preset {
+	/// The standard bindings.
+	#[default]
+	Default,
+	/// The standard bindings extended with vim style bindings.
+	Vim,
+}
+
+impl Preset {
+	/// The bindings of this preset for `mode`.
+	fn bindings(&self, mode: Mode) -> Vec<Binding> {
+		let bindings: &[(&str, Action)] = match mode {
+			Mode::Normal => &[
+				("q", Action::Quit),
+				("r", Action::Reload),
+				("tab", Action::NextTab),
+				("shift+tab", Action::PreviousTab),
+				("i", Action::StartTrackInput),
+				("/", Action::StartSearch),
+				("w", Action::SaveView),
+				("x", Action::SendSignal),
+				("n", Action::Renice),
+				("o", Action::SetIoPriority),
+				("a", Action::SetAffinity),
+				("f1", Action::ToggleDebug),
+				("c", Action::ToggleCpuView),
+				("up", Action::SelectUp),
+				("down", Action::SelectDown),
+				("pageup", Action::SelectPageUp),
+				("pagedown", Action::SelectPageDown),
+				("home", Action::SelectFirst),
+				("end", Action::SelectLast),
+				("s", Action::SortNext),
+				("S", Action::SortPrevious),
+				("I", Action::ToggleSortOrder),
+				("t", Action::ToggleProcessTree),
+				("space", Action::ToggleCollapsed),
+				("p", Action::SelectParent),
+				("esc", Action::ClearStatus),
+			],
+			Mode::Input => &[
+				("enter", Action::Confirm),
+				("esc", Action::Cancel),
+				("backspace", Action::DeleteBackward),
+				("left", Action::CursorLeft),
+				("right", Action::CursorRight),
+				("up", Action::CursorStart),
+				("down", Action::CursorEnd),
+				("home", Action::CursorStart),
+				("end", Action::CursorEnd),
+				("tab", Action::NextTab),
+				("shift+tab", Action::PreviousTab),
+			],
+			Mode::Dialog => &[
+				("enter", Action::Confirm),
+				("y", Action::Confirm),
+				("esc", Action::Cancel),
+				("n", Action::Cancel),
+				("up", Action::SelectUp),
+				("down", Action::SelectDown),
+			],
+		};
+
+		let vim: &[(&str, Action)] = match (self, mode) {
+			(Self::Default, _) => &[],
+			(Self::Vim, Mode::Normal) => &[
+				("Z Z", Action::Quit),
+				(": q", Action::Quit),
+				("ctrl+c", Action::Quit),
+				("g t", Action::NextTab),
+				("g T", Action::PreviousTab),
+				("l", Action::NextTab),
+				("h", Action::PreviousTab),
+				("k", Action::SelectUp),
+				("j", Action::SelectDown),
+				("ctrl+u", Action::SelectPageUp),
+				("ctrl+d", Action::SelectPageDown),
+				("g g", Action::SelectFirst),
+				("G", Action::SelectLast),
+			],
+			(Self::Vim, Mode::Input) => &[
+				("ctrl+c", Action::Cancel),
+				("ctrl+h", Action::DeleteBackward),
+			],
+			(Self::Vim, Mode::Dialog) => &[
+				("ctrl+c", Action::Cancel),
+				("k", Action::SelectUp),
+				("j", Action::SelectDown),
+			],
+		};
+
+		bindings
+			.iter()
+			.chain(vim)
+			.map(|(keys, action)| Binding {
+				keys: keys.parse().expect("preset bindings are valid"),
+				action: *action,
+			})
+			.collect()
+	}
+}
+
+/// The key bindings for every mode.
+///
+/// In the configuration file a `preset` is chosen, whose bindings are then overridden per
+/// action, with a single key sequence or a list of them. An empty list unbinds the action.
+/// It is written as the default preset with the keys of every action, so it reads back the same.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(try_from = "KeymapRepr", into = "KeymapRepr")]
+pub struct Keymap {
+	pub normal: Vec<Binding>,
+	pub input: Vec<Binding>,
+	pub dialog: Vec<Binding>,
+}
+
+impl Keymap {
+	/// Looks up the keys pressed so far in the bindings of `mode`.
+	///
+	/// A binding matching exactly takes precedence over longer ones starting with the same keys.
+	pub fn lookup(&self, mode: Mode, keys: &[Key]) -> Lookup {
+		let bindings = self.bindings(mode);
+
+		if let Some(binding) = bindings.iter().find(|binding| binding.keys.0 == keys) {
+			return Lookup::Action(binding.action);
+		}
+
+		if bindings
+			.iter()
+			.any(|binding| binding.keys.0.starts_with(keys))
+		{
+			Lookup::Pending
+		} else {
+			Lookup::None
+		}
+	}
+
+	/// Gets the keys bound to `action` in `mode`, for displaying.
+	pub fn keys(&self, mode: Mode, action: Action) -> impl Iterator<Item = &KeySequence> {
+		self.bindings(mode)
+			.iter()
+			.filter(move |binding| binding.action == action)
+			.map(|binding| &binding.keys)
+	}
+
+	fn bindings(&self, mode: Mode) -> &[Binding] {
+		match mode {
+			Mode::Normal => &self.normal,
+			Mode::Input => &self.input,
+			Mode::Dialog => &self.dialog,
+		}
+	}
+}
+
+impl Default for Keymap {
+	fn default() -> Self {
+		Keymap {
+			normal: Preset::Default.bindings(Mode::Normal),
+			input: Preset::Default.bindings(Mode::Input),
+			dialog: Preset::Default.bindings(Mode::Dialog),
+		}
+	}
+}
+
+/// The way the keymap is written in the configuration file.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct KeymapRepr {
+	preset: Preset,
+	normal: BTreeMap<Action, serde_yaml::Value>,
+	input: BTreeMap<Action, serde_yaml::Value>,
+	dialog: BTreeMap<Action, serde_yaml::Value>,
+}
+
+impl TryFrom<KeymapRepr> for Keymap {
+	type Error = String;
+
+	fn try_from(value: KeymapRepr) -> Result<Self, Self::Error> {
+		Ok(Keymap {
+			normal: bind(value.preset.bindings(Mode::Normal), value.normal)?,
+			input: bind(value.preset.bindings(Mode::Input), value.input)?,
+			dialog: bind(value.preset.bindings(Mode::Dialog), value.dialog)?,
+		})
+	}
+}
+
+impl From<Keymap> for KeymapRepr {
+	fn from(keymap: Keymap) -> Self {
+		let preset = Preset::Default;
+		KeymapRepr {
+			preset,
+			normal: overrides(preset.bindings(Mode::Normal), keymap.normal),
+			input: overrides(preset.bindings(Mode::Input), keymap.input),
+			dialog: overrides(preset.bindings(Mode::Dialog), keymap.dialog),
+		}
+	}
+}
+
+/// Lists the keys of every action in `bindings`, and of every action of `preset` missing from
+/// them as an empty list, which unbinds it.
+fn overrides(preset: Vec<Binding>, bindings: Vec<Binding>) -> BTreeMap<Action, serde_yaml::Value> {
+	let mut overrides: BTreeMap<Action, Vec<serde_yaml::Value>> = preset
+		.into_iter()
+		.map(|binding| (binding.action, Vec::new()))
+		.collect();
+
+	for binding in bindings {
+		overrides
+			.entry(binding.action)
+			.or_default()
+			.push(serde_yaml::Value::String(binding.keys.to_string()));
+	}
+
+	overrides
+		.into_iter()
+		.map(|(action, keys)| (action, serde_yaml::Value::Sequence(keys)))
+		.collect()
+}
+
+/// Replaces the bindings of every action in `overrides`.
+///
+/// Bindings of other actions using the same keys are removed, so the configured ones win.
+fn bind(
+	mut bindings: Vec<Binding>,
+	overrides: BTreeMap<Action, serde_yaml::Value>,
+) -> Result<Vec<Binding>, String> {
+	for (action, value) in overrides {
+		let keys = match value {
+			serde_yaml::Value::Null => Vec::new(),
+			serde_yaml::Value::String(keys) => vec![keys],
+			serde_yaml::Value::Sequence(sequence) => sequence
+				.into_iter()
+				.map(|keys| match keys {
+					serde_yaml::Value::String(keys) => Ok(keys),
+					_ => Err(String::from("expected a key like `q` or `ctrl+c`")),
+				})
+				.collect::<Result<_, _>>()?,
+			_ => return Err(String::from("expected a key or a list of keys")),
+		};
+
+		let keys = keys
+			.iter()
+			.map(|keys| keys.parse())
+			.collect::<Result<Vec<KeySequence>, _>>()?;
+
+		bindings.retain(|binding| binding.action != action && !keys.contains(&binding.keys));
+		bindings.extend(keys.into_iter().map(|keys| Binding { keys, action }));
+	}
+
+	Ok(bindings)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn key(s: &str) -> Key {
+		s.parse().unwrap()
+	}
+
+	fn keys(s: &str) -> Vec<Key> {
+		s.parse::<KeySequence>().unwrap().0
+	}
+
+	fn keymap(yaml: &str) -> Keymap {
+		serde_yaml::from_str(yaml).unwrap()
+	}
+
+	#[test]
+	fn parse_keys() {
+		assert_eq!(key("q"), Key::new(KeyCode::Char('q')));
+		assert_eq!(key("G"), Key::new(KeyCode::Char('G')));
+		assert_eq!(key("shift+g"), Key::new(KeyCode::Char('G')));
+		assert_eq!(key("shift+tab"), Key::new(KeyCode::BackTab));
+		assert_eq!(key("space"), Key::new(KeyCode::Char(' ')));
+		assert_eq!(key("F12"), Key::new(KeyCode::F(12)));
+		assert_eq!(
+			key("Ctrl+Alt+Delete"),
+			Key {
+				code: KeyCode::Delete,
+				modifiers: KeyModifiers::CONTROL | KeyModifiers::ALT,
+			}
+		);
+		assert_eq!(
+			key("ctrl++"),
+			Key {
+				code: KeyCode::Char('+'),
+				modifiers: KeyModifiers::CONTROL,
+			}
+		);
+		assert_eq!(key("+"), Key::new(KeyCode::Char('+')));
+
+		assert_eq!(
+			"super+a".parse::<Key>(),
+			Err(String::from(
+				"unknown modifier `super` in `super+a`, expected ctrl, alt or shift"
+			))
+		);
+		assert_eq!("f25".parse::<Key>(), Err(String::from("unknown key `f25`")));
+		assert_eq!(
+			"enterr".parse::<Key>(),
+			Err(String::from("unknown key `enterr`"))
+		);
+		assert_eq!(
+			" ".parse::<KeySequence>(),
+			Err(String::from("a key binding can't be empty"))
+		);
+	}
+
+	#[test]
+	fn display_keys() {
+		for s in [
+			"q",
+			"G",
+			"ctrl+c",
+			"ctrl+alt+delete",
+			"shift+tab",
+			"shift+up",
+			"space",
+			"f1",
+			"pageup",
+			"esc",
+			"ctrl++",
+		] {
+			assert_eq!(key(s).to_string(), s);
+			assert_eq!(key(&key(s).to_string()), key(s));
+		}
+		assert_eq!("g  T".parse::<KeySequence>().unwrap().to_string(), "g T");
+	}
+
+	#[test]
+	fn lookup_sequences() {
+		let keymap = keymap("preset: vim");
+
+		assert_eq!(
+			keymap.lookup(Mode::Normal, &keys("q")),
+			Lookup::Action(Action::Quit)
+		);
+		assert_eq!(keymap.lookup(Mode::Normal, &keys("g")), Lookup::Pending);
+		assert_eq!(
+			keymap.lookup(Mode::Normal, &keys("g t")),
+			Lookup::Action(Action::NextTab)
+		);
+		assert_eq!(
+			keymap.lookup(Mode::Normal, &keys("g g")),
+			Lookup::Action(Action::SelectFirst)
+		);
+		assert_eq!(keymap.lookup(Mode::Normal, &keys("g x")), Lookup::None);
+		assert_eq!(keymap.lookup(Mode::Normal, &keys("Z")), Lookup::Pending);
+		assert_eq!(keymap.lookup(Mode::Input, &keys("q")), Lookup::None);
+		assert_eq!(
+			keymap.lookup(Mode::Dialog, &keys("j")),
+			Lookup::Action(Action::SelectDown)
+		);
+
+		// A binding matching exactly wins over a longer one starting with it.
+		let keymap = self::keymap("normal:\n  quit: g\n  nextTab: g t");
+		assert_eq!(
+			keymap.lookup(Mode::Normal, &keys("g")),
+			Lookup::Action(Action::Quit)
+		);
+	}
+
+	#[test]
+	fn overrides_remove_conflicts() {
+		let keymap = keymap("normal:\n  reload: q\n  toggleDebug: []\n  quit: [ctrl+q, Z Z]");
+
+		assert_eq!(
+			keymap.lookup(Mode::Normal, &keys("q")),
+			Lookup::Action(Action::Reload)
+		);
+		assert_eq!(keymap.lookup(Mode::Normal, &keys("r")), Lookup::None);
+		assert_eq!(keymap.lookup(Mode::Normal, &keys("f1")), Lookup::None);
+		assert_eq!(
+			keymap
+				.keys(Mode::Normal, Action::Quit)
+				.map(KeySequence::to_string)
+				.collect::<Vec<_>>(),
+			["ctrl+q", "Z Z"]
+		);
+		// Other modes keep their bindings.
+		assert_eq!(
+			keymap.lookup(Mode::Input, &keys("enter")),
+			Lookup::Action(Action::Confirm)
+		);
+
+		assert!(serde_yaml::from_str::<Keymap>("normal:\n  quit: ctrl+x+y").is_err());
+		assert!(serde_yaml::from_str::<Keymap>("normal:\n  quit: 1").is_err());
+		assert!(serde_yaml::from_str::<Keymap>("normal:\n  fly: f").is_err());
+	}
+
+	#[test]
+	fn round_trip() {
+		for yaml in [
+			"{}",
+			"preset: vim",
+			"preset: vim\nnormal:\n  reload: q\n  toggleDebug: []\ndialog:\n  confirm: space",
+		] {
+			let keymap = keymap(yaml);
+			let written = serde_yaml::to_string(&keymap).unwrap();
+			let read: Keymap = serde_yaml::from_str(&written).unwrap();
+
+			for mode in [Mode::Normal, Mode::Input, Mode::Dialog] {
+				let mut expected: Vec<_> = keymap
+					.bindings(mode)
+					.iter()
+					.map(|binding| (binding.action, binding.keys.to_string()))
+					.collect();
+				let mut actual: Vec<_> = read
+					.bindings(mode)
+					.iter()
+					.map(|binding| (binding.action, binding.keys.to_string()))
+					.collect();
+				expected.sort();
+				actual.sort();
+				assert_eq!(actual, expected, "{}", written);
+			}
+		}
+	}
+}
@@ -0,0 +1,625 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::terminal::Screen;
+
+/// A window that can be placed in a layout.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Module {
+	/// A summary of the system and the load average graph.
+	Stats,
+	/// The total cpu usage graph.
+	CpuUsage,
+	/// The frequency of every core and its scaling settings.
+	CpuFrequency,
+	/// The memory and swap graphs.
+	Memory,
+	/// The read and write throughput graph of all block devices.
+	DiskIo,
+	/// The activity of every block device.
+	DiskDevices,
+	/// The usage of every mounted filesystem.
+	DiskFilesystems,
+	/// The incoming and outgoing network graphs and the interface table side by side.
+	Network,
+	/// The incoming network graph.
+	NetworkIn,
+	/// The outgoing network graph.
+	NetworkOut,
+	/// The traffic of every network interface.
+	NetworkInterfaces,
+	/// The temperature of every sensor.
+	Sensors,
+	/// The pressure stall information of the cpu, memory and io.
+	Pressure,
+	/// The process table.
+	Processes,
+	/// Information about the tracked process.
+	TrackedInfo,
+	/// The cpu usage graph of the tracked process.
+	TrackedCpuUsage,
+	/// The memory graph of the tracked process.
+	TrackedMemory,
+	/// The log messages.
+	DebugLog,
+	/// The real and processing tick times.
+	DebugTicks,
+	/// The refresh, draw and event tick times.
+	DebugTickParts,
+}
+
+impl Module {
+	/// Every module, in the order they are listed in error messages.
+	pub const ALL: [Module; 20] = [
+		Self::Stats,
+		Self::CpuUsage,
+		Self::CpuFrequency,
+		Self::Memory,
+		Self::DiskIo,
+		Self::DiskDevices,
+		Self::DiskFilesystems,
+		Self::Network,
+		Self::NetworkIn,
+		Self::NetworkOut,
+		Self::NetworkInterfaces,
+		Self::Sensors,
+		Self::Pressure,
+		Self::Processes,
+		Self::TrackedInfo,
+		Self::TrackedCpuUsage,
+		Self::TrackedMemory,
+		Self::DebugLog,
+		Self::DebugTicks,
+		Self::DebugTickParts,
+	];
+
+	/// Gets the name used in the configuration file.
+	pub const fn as_str(&self) -> &'static str {
+		match *self {
+			Self::Stats => "stats",
+			Self::CpuUsage => "cpu_usage",
+			Self::CpuFrequency => "cpu_frequency",
+			Self::Memory => "memory",
+			Self::DiskIo => "disk.io",
+			Self::DiskDevices => "disk.devices",
+			Self::DiskFilesystems => "disk.filesystems",
+			Self::Network => "network",
+			Self::NetworkIn => "network.in",
+			Self::NetworkOut => "network.out",
+			Self::NetworkInterfaces => "network.interfaces",
+			Self::Sensors => "sensors",
+			Self::Pressure => "pressure",
+			Self::Processes => "processes",
+			Self::TrackedInfo => "tracked.info",
+			Self::TrackedCpuUsage => "tracked.cpu_usage",
+			Self::TrackedMemory => "tracked.memory",
+			Self::DebugLog => "debug.log",
+			Self::DebugTicks => "debug.ticks",
+			Self::DebugTickParts => "debug.tick_parts",
+		}
+	}
+
+	/// Whether this module displays the tracked process.
+	pub const fn is_tracked(&self) -> bool {
+		matches!(
+			self,
+			Self::TrackedInfo | Self::TrackedCpuUsage | Self::TrackedMemory
+		)
+	}
+}
+
+impl FromStr for Module {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Module::ALL
+			.into_iter()
+			.find(|module| module.as_str() == s)
+			.ok_or_else(|| {
+				let names: Vec<_> = Module::ALL.iter().map(Module::as_str).collect();
+				format!(
+					"unknown module `{}`, expected one of {}",
+					s,
+					names.join(", ")
+				)
+			})
+	}
+}
+
+impl Serialize for Module {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(self.as_str())
+	}
+}
+
+/// How the `cpu_usage` module shows the usage.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum CpuView {
+	/// A single graph of the average usage of all cores.
+	#[default]
+	Aggregate,
+	/// A grid with a small graph for every core.
+	Cores,
+	/// A row for every core, shaded by its usage.
+	Heatmap,
+	/// The time spent in every state like user, system or iowait, stacked, in total and per core.
+	Times,
+}
+
+impl CpuView {
+	/// The view shown after this one when toggling.
+	pub fn next(self) -> Self {
+		match self {
+			Self::Aggregate => Self::Cores,
+			Self::Cores => Self::Heatmap,
+			Self::Heatmap => Self::Times,
+			Self::Times => Self::Aggregate,
+		}
+	}
+}
+
+/// A column of the process table.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SortColumn {
+	Pid,
+	#[default]
+	Name,
+	Memory,
+	Cpu,
+	Time,
+	Written,
+	Read,
+}
+
+impl SortColumn {
+	/// Every column, in the order they are shown.
+	pub const ALL: [SortColumn; 7] = [
+		Self::Pid,
+		Self::Name,
+		Self::Memory,
+		Self::Cpu,
+		Self::Time,
+		Self::Written,
+		Self::Read,
+	];
+
+	/// The column shown after this one, wrapping around.
+	pub fn next(self) -> Self {
+		let index = Self::ALL
+			.iter()
+			.position(|&column| column == self)
+			.unwrap_or_default();
+		Self::ALL[(index + 1) % Self::ALL.len()]
+	}
+
+	/// The column shown before this one, wrapping around.
+	pub fn previous(self) -> Self {
+		let index = Self::ALL
+			.iter()
+			.position(|&column| column == self)
+			.unwrap_or_default();
+		Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+	}
+}
+
+/// How the process table is sorted.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ProcessSort {
+	/// The column the processes are sorted by, processes with equal values are sorted by name.
+	pub column: SortColumn,
+	/// Sorts from the highest to the lowest value.
+	pub descending: bool,
+}
+
+/// The size of a node along the direction of its parent.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Size {
+	/// A fixed amount of cells, written as `10`.
+	Length(u16),
+	/// A percentage of the parent, written as `30%`.
+	Percentage(u16),
+	/// A fraction of the parent, written as `1/3`.
+	Ratio(u32, u32),
+}
+
+impl Size {
+	/// Gets the amount of cells this size takes up out of `total`.
+	pub fn length(&self, total: u16) -> u16 {
+		let length = match *self {
+			Self::Length(length) => length as u32,
+			Self::Percentage(percentage) => total as u32 * percentage as u32 / 100,
+			Self::Ratio(numerator, denominator) => {
+				(total as u64 * numerator as u64 / denominator as u64) as u32
+			},
+		};
+		length.min(total as u32) as u16
+	}
+}
+
+impl FromStr for Size {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let invalid = || {
+			format!(
+				"invalid size `{}`, expected a length like `10`, a percentage like `30%` or a ratio like `1/3`",
+				s
+			)
+		};
+
+		if let Some(percentage) = s.strip_suffix('%') {
+			let percentage: u16 = percentage.trim().parse().map_err(|_| invalid())?;
+			if percentage > 100 {
+				return Err(format!("percentage `{}` is larger than 100%", s));
+			}
+			Ok(Self::Percentage(percentage))
+		} else if let Some((numerator, denominator)) = s.split_once('/') {
+			let numerator: u32 = numerator.trim().parse().map_err(|_| invalid())?;
+			let denominator: u32 = denominator.trim().parse().map_err(|_| invalid())?;
+			if denominator == 0 || numerator > denominator {
+				return Err(format!("ratio `{}` must be between 0 and 1", s));
+			}
+			Ok(Self::Ratio(numerator, denominator))
+		} else {
+			s.parse().map(Self::Length).map_err(|_| invalid())
+		}
+	}
+}
+
+impl Display for Size {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match *self {
+			Self::Length(length) => write!(f, "{}", length),
+			Self::Percentage(percentage) => write!(f, "{}%", percentage),
+			Self::Ratio(numerator, denominator) => write!(f, "{}/{}", numerator, denominator),
+		}
+	}
+}
+
+impl Serialize for Size {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
+}
+
+/// What a node of a layout contains.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Content {
+	/// A single module.
+	Module(Module),
+	/// Nodes placed next to each other from left to right.
+	Row(Vec<Node>),
+	/// Nodes placed below each other from top to bottom.
+	Column(Vec<Node>),
+}
+
+/// A node of a layout.
+///
+/// In the configuration file this is either the name of a module, or a map with one of
+/// the keys `module`, `row` or `column` and an optional `size`.
+/// Nodes without a size share the space left by their siblings equally.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(try_from = "serde_yaml::Value")]
+pub struct Node {
+	#[serde(flatten)]
+	pub content: Content,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub size: Option<Size>,
+}
+
+impl Node {
+	/// Creates a node containing `module`.
+	pub const fn module(module: Module) -> Self {
+		Node {
+			content: Content::Module(module),
+			size: None,
+		}
+	}
+
+	/// Creates a row of `children`.
+	pub fn row<const N: usize>(children: [Node; N]) -> Self {
+		Node {
+			content: Content::Row(Vec::from(children)),
+			size: None,
+		}
+	}
+
+	/// Creates a column of `children`.
+	pub fn column<const N: usize>(children: [Node; N]) -> Self {
+		Node {
+			content: Content::Column(Vec::from(children)),
+			size: None,
+		}
+	}
+
+	/// Sets the size of this node.
+	pub const fn sized(mut self, size: Size) -> Self {
+		self.size = Some(size);
+		self
+	}
+
+	/// Whether every module in this node displays the tracked process.
+	pub fn is_tracked(&self) -> bool {
+		match &self.content {
+			Content::Module(module) => module.is_tracked(),
+			Content::Row(children) | Content::Column(children) => {
+				children.iter().all(Node::is_tracked)
+			},
+		}
+	}
+
+	/// Whether `module` is drawn anywhere in this node.
+	pub fn contains(&self, module: Module) -> bool {
+		match &self.content {
+			Content::Module(own) => *own == module,
+			Content::Row(children) | Content::Column(children) => {
+				children.iter().any(|child| child.contains(module))
+			},
+		}
+	}
+
+	/// The layout drawn by `screen` when a tab has no layout of its own.
+	pub fn builtin(screen: Screen) -> Self {
+		match screen {
+			Screen::Default | Screen::Custom => Node::column([
+				Node::row([
+					Node::module(Module::Stats),
+					Node::module(Module::CpuFrequency),
+				]),
+				Node::module(Module::CpuUsage),
+				Node::module(Module::Memory),
+				Node::module(Module::Network),
+			]),
+			Screen::Processes => Node::module(Module::Processes),
+			Screen::Disks => Node::column([
+				Node::module(Module::DiskIo),
+				Node::module(Module::DiskDevices),
+				Node::module(Module::DiskFilesystems),
+			]),
+			Screen::Tracked => Node::column([
+				Node::module(Module::TrackedInfo).sized(Size::Length(8)),
+				Node::row([
+					Node::module(Module::TrackedCpuUsage),
+					Node::module(Module::TrackedMemory),
+				]),
+			]),
+			Screen::Debug => Node::column([
+				Node::row([
+					Node::module(Module::DebugTicks),
+					Node::module(Module::DebugTickParts),
+				]),
+				Node::module(Module::DebugLog),
+			]),
+		}
+	}
+}
+
+impl TryFrom<serde_yaml::Value> for Node {
+	type Error = String;
+
+	fn try_from(value: serde_yaml::Value) -> Result<Self, Self::Error> {
+		let mapping =
+			match value {
+				serde_yaml::Value::String(module) => return Ok(Node::module(module.parse()?)),
+				serde_yaml::Value::Mapping(mapping) => mapping,
+				_ => return Err(String::from(
+					"expected the name of a module or a map with a `module`, `row` or `column` key",
+				)),
+			};
+
+		let mut content = None;
+		let mut size = None;
+
+		for (key, value) in mapping {
+			let key = match key {
+				serde_yaml::Value::String(key) => key,
+				_ => return Err(String::from("layout keys must be strings")),
+			};
+
+			let new_content = match key.as_str() {
+				"size" => {
+					size = Some(match value {
+						serde_yaml::Value::String(s) => s.parse()?,
+						serde_yaml::Value::Number(n) => n.to_string().parse()?,
+						_ => return Err(String::from("expected a size like `10`, `30%` or `1/3`")),
+					});
+					continue;
+				},
+				"module" => match value {
+					serde_yaml::Value::String(module) => Content::Module(module.parse()?),
+					_ => return Err(String::from("expected the name of a module")),
+				},
+				"row" => Content::Row(children(value)?),
+				"column" => Content::Column(children(value)?),
+				key => {
+					return Err(format!(
+						"unknown layout key `{}`, expected one of `module`, `row`, `column` or `size`",
+						key
+					))
+				},
+			};
+
+			if content.replace(new_content).is_some() {
+				return Err(String::from(
+					"a layout node can only have one of `module`, `row` or `column`",
+				));
+			}
+		}
+
+		match content {
+			Some(content) => Ok(Node { content, size }),
+			None => Err(String::from(
+				"a layout node needs one of `module`, `row` or `column`",
+			)),
+		}
+	}
+}
+
+/// Parses the children of a row or column.
+fn children(value: serde_yaml::Value) -> Result<Vec<Node>, String> {
+	let sequence = match value {
+		serde_yaml::Value::Sequence(sequence) => sequence,
+		_ => return Err(String::from("expected a list of layout nodes")),
+	};
+
+	if sequence.is_empty() {
+		return Err(String::from("a row or column needs at least one node"));
+	}
+
+	sequence.into_iter().map(Node::try_from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn node(yaml: &str) -> Result<Node, String> {
+		Node::try_from(serde_yaml::from_str::<serde_yaml::Value>(yaml).unwrap())
+	}
+
+	#[test]
+	fn sizes() {
+		for (s, size, length_of_60) in [
+			("10", Size::Length(10), 10),
+			(" 0 ", Size::Length(0), 0),
+			("100", Size::Length(100), 60),
+			("30%", Size::Percentage(30), 18),
+			("100 %", Size::Percentage(100), 60),
+			("0%", Size::Percentage(0), 0),
+			("1/3", Size::Ratio(1, 3), 20),
+			("2 / 3", Size::Ratio(2, 3), 40),
+			("1/1", Size::Ratio(1, 1), 60),
+			("0/5", Size::Ratio(0, 5), 0),
+		] {
+			assert_eq!(s.parse(), Ok(size), "{}", s);
+			assert_eq!(size.length(60), length_of_60, "{}", s);
+			assert_eq!(size.to_string().parse(), Ok(size), "{}", s);
+		}
+
+		assert_eq!(Size::Length(u16::MAX).length(u16::MAX), u16::MAX);
+		assert_eq!(Size::Ratio(u32::MAX, u32::MAX).length(u16::MAX), u16::MAX);
+	}
+
+	#[test]
+	fn invalid_sizes() {
+		for (s, error) in [
+			("101%", "percentage `101%` is larger than 100%"),
+			("3/2", "ratio `3/2` must be between 0 and 1"),
+			("1/0", "ratio `1/0` must be between 0 and 1"),
+			(
+				"-1",
+				"invalid size `-1`, expected a length like `10`, a percentage like `30%` or a ratio like `1/3`",
+			),
+			(
+				"a%",
+				"invalid size `a%`, expected a length like `10`, a percentage like `30%` or a ratio like `1/3`",
+			),
+			(
+				"1/",
+				"invalid size `1/`, expected a length like `10`, a percentage like `30%` or a ratio like `1/3`",
+			),
+			(
+				"",
+				"invalid size ``, expected a length like `10`, a percentage like `30%` or a ratio like `1/3`",
+			),
+		] {
+			assert_eq!(s.parse::<Size>(), Err(String::from(error)), "{}", s);
+		}
+	}
+
+	#[test]
+	fn modules() {
+		for module in Module::ALL {
+			assert_eq!(module.as_str().parse(), Ok(module));
+			assert_eq!(node(module.as_str()), Ok(Node::module(module)));
+		}
+
+		for name in ["cpu", "Processes", "tracked.", ""] {
+			let error = name.parse::<Module>().unwrap_err();
+			assert!(
+				error.starts_with(&format!(
+					"unknown module `{}`, expected one of stats, ",
+					name
+				)),
+				"{}",
+				error
+			);
+			assert_eq!(node(&format!("module: \"{}\"", name)), Err(error));
+		}
+	}
+
+	#[test]
+	fn nested_nodes() {
+		let layout = node(
+			"
+column:
+  - module: stats
+    size: 4
+  - row:
+      - cpu_usage
+      - module: processes
+        size: 2/3
+    size: 50%
+  - column: [memory, network]
+",
+		);
+
+		assert_eq!(
+			layout,
+			Ok(Node::column([
+				Node::module(Module::Stats).sized(Size::Length(4)),
+				Node::row([
+					Node::module(Module::CpuUsage),
+					Node::module(Module::Processes).sized(Size::Ratio(2, 3)),
+				])
+				.sized(Size::Percentage(50)),
+				Node::column([Node::module(Module::Memory), Node::module(Module::Network)]),
+			]))
+		);
+		let layout = layout.unwrap();
+		assert!(layout.contains(Module::Processes));
+		assert!(!layout.contains(Module::DiskIo));
+		assert!(!layout.is_tracked());
+	}
+
+	#[test]
+	fn invalid_nodes() {
+		for (yaml, error) in [
+			(
+				"3",
+				"expected the name of a module or a map with a `module`, `row` or `column` key",
+			),
+			(
+				"size: 3",
+				"a layout node needs one of `module`, `row` or `column`",
+			),
+			(
+				"{module: stats, row: [memory]}",
+				"a layout node can only have one of `module`, `row` or `column`",
+			),
+			(
+				"{module: stats, width: 3}",
+				"unknown layout key `width`, expected one of `module`, `row`, `column` or `size`",
+			),
+			("row: []", "a row or column needs at least one node"),
+			("column: memory", "expected a list of layout nodes"),
+			(
+				"{module: stats, size: 200%}",
+				"percentage `200%` is larger than 100%",
+			),
+			("{module: [stats]}", "expected the name of a module"),
+			(
+				"row: [stats, {column: [memory, {size: 1}]}]",
+				"a layout node needs one of `module`, `row` or `column`",
+			),
+		] {
+			assert_eq!(node(yaml), Err(String::from(error)), "{}", yaml);
+		}
+	}
+}
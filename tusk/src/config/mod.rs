@@ -1,23 +1,387 @@
+/// How much data is collected and how it is scaled.
+pub mod datapoints;
+/// Errors that can occur while loading the configuration.
+pub mod error;
+/// The query language deciding which processes are listed.
+pub mod filter;
+/// The key bindings.
+pub mod keys;
+/// The arrangement of modules on a tab.
+pub mod layout;
+/// Parsers for values in the configuration.
+pub mod parse;
+/// The tabs shown in the top bar.
+pub mod tabs;
+/// The colors and styles used for drawing.
 pub mod theme;
+/// Watching the configuration files for changes.
+pub mod watch;
 
-use std::{ffi::OsString, sync::Arc};
+use std::{
+	env, fs,
+	path::{Path, PathBuf},
+	sync::{Arc, PoisonError, RwLock},
+	time::Duration,
+};
 
 use lazy_static::lazy_static;
+use memu::units::MegaByte;
 use serde::{Deserialize, Serialize};
 
+use self::{
+	datapoints::{Collectors, History, Interfaces, NetworkGraphs, Sampling},
+	error::ConfigError,
+	filter::Filter,
+	keys::Keymap,
+	layout::{CpuView, ProcessSort},
+	parse::{duration, unit},
+	tabs::{default_tabs, Tab},
+	theme::{self as theme_mod, Theme},
+};
+use crate::{cli::Args, sampler, terminal::Screen};
+
 lazy_static! {
-	pub static ref CONFIG: Arc<Config> = Arc::new(Config::load());
+	pub static ref CONFIG: RwLock<Arc<Config>> = RwLock::new(Arc::new(Config::default()));
+}
+
+/// The name of the configuration file searched for in the config directories.
+const CONFIG_FILE: &str = "config.yaml";
+
+/// The name of the file views are saved to, next to the configuration file.
+const VIEWS_FILE: &str = "views.yaml";
+
+/// Returns the currently active configuration.
+pub fn current() -> Arc<Config> {
+	Arc::clone(&CONFIG.read().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Loads the configuration and its theme and makes them the active ones.
+/// Values given on the command line override the ones in the configuration file.
+///
+/// If loading fails the defaults are used and the error is returned,
+/// so it can be displayed to the user.
+pub fn init(args: &Args) -> Result<(), ConfigError> {
+	let (mut config, config_result) = match Config::load(args.config.as_deref()) {
+		Ok(config) => (config, Ok(())),
+		Err(e) => (Config::default(), Err(e)),
+	};
+	args.apply(&mut config);
+
+	let (config, config_result) = match config_result.and_then(|_| config.validate()) {
+		Ok(()) => (config, Ok(())),
+		Err(e) => {
+			let mut config = Config::default();
+			args.apply(&mut config);
+			(config, Err(e))
+		},
+	};
+
+	let (theme, theme_result) = match config.load_theme() {
+		Ok(theme) => (theme, Ok(())),
+		Err(e) if config.no_color => (Theme::default().without_color(), Err(e)),
+		Err(e) => (Theme::default(), Err(e)),
+	};
+
+	*CONFIG.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(config);
+	theme_mod::set(theme);
+
+	config_result.and(theme_result)
+}
+
+/// Loads the configuration and its theme again and makes them the active ones.
+///
+/// Unlike [`init`] the active configuration and theme are kept if loading fails.
+pub fn reload(args: &Args) -> Result<(), ConfigError> {
+	let mut config = Config::load(args.config.as_deref())?;
+	args.apply(&mut config);
+	config.validate()?;
+	let theme = config.load_theme()?;
+
+	*CONFIG.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(config);
+	theme_mod::set(theme);
+
+	Ok(())
 }
 
-#[derive(Serialize, Deserialize)]
+/// The application configuration.
+///
+/// Every key is optional, missing keys fall back to their default value.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Config {
-	theme: OsString,
+	/// The theme to use, either the name of a built-in theme or the path to a theme file.
+	pub theme: Option<String>,
+	/// Draws without any colors, using only text attributes.
+	pub no_color: bool,
+	/// How long a tick should be, the terminal is drawn once every tick.
+	#[serde(with = "duration")]
+	pub tick: Duration,
+	/// How often data is collected.
+	pub sampling: Sampling,
+	/// Enables or disables collectors by name, all collectors are enabled by default.
+	pub collectors: Collectors,
+	/// How the cpu usage is shown at start.
+	pub cpu_view: CpuView,
+	/// How the process table is sorted at start.
+	pub process_sort: ProcessSort,
+	/// The tabs shown in the top bar, in order.
+	pub tabs: Vec<Tab>,
+	/// Named process filters, each shown as a tab after `tabs`.
+	pub views: Vec<View>,
+	/// The key bindings.
+	pub keys: Keymap,
+	/// How many datapoints each graph keeps.
+	pub history: History,
+	/// Reloads the configuration whenever the configuration or theme file changes.
+	pub watch: bool,
+	/// How many log messages should be keept track of.
+	pub log_messages: usize,
+	/// Captures the mouse, so its wheel scrolls the process table. Only read at start.
+	pub mouse: bool,
+	/// The unit and scaling of the network graphs.
+	pub network: NetworkGraphs,
+	/// The network interfaces sampled.
+	pub interfaces: Interfaces,
+	/// The amount of memory displayed at minimum by the tracked process.
+	#[serde(with = "unit")]
+	pub tracked_minimum_memory: MegaByte,
+	/// The file this configuration was loaded from.
+	#[serde(skip)]
+	pub path: Option<PathBuf>,
 }
 
-impl Config {
-	pub fn load() -> Self {
+impl Default for Config {
+	fn default() -> Self {
 		Config {
-			theme: OsString::from("../config.yaml"),
+			theme: None,
+			no_color: false,
+			tick: Duration::from_millis(50),
+			sampling: Sampling::default(),
+			collectors: Collectors::default(),
+			cpu_view: CpuView::default(),
+			process_sort: ProcessSort::default(),
+			tabs: default_tabs(),
+			views: Vec::new(),
+			keys: Keymap::default(),
+			history: History::default(),
+			watch: false,
+			log_messages: 100,
+			mouse: true,
+			network: NetworkGraphs::default(),
+			interfaces: Interfaces::default(),
+			tracked_minimum_memory: MegaByte::from_u8(0),
+			path: None,
 		}
 	}
 }
+
+impl Config {
+	/// Loads the configuration.
+	///
+	/// If `path` is given that file is loaded. Otherwise the first existing file out of
+	/// `$XDG_CONFIG_HOME/tusk/config.yaml` and `$HOME/.config/tusk/config.yaml` is used.
+	/// When no file is found the default configuration is returned.
+	///
+	/// The views saved in the views file are added to the views of the configuration.
+	pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+		let mut config = match path {
+			Some(path) => Self::from_file(path)?,
+			None => match Self::discover() {
+				Some(path) => Self::from_file(&path)?,
+				None => Self::default(),
+			},
+		};
+
+		if let Some(views) = config.views_path().filter(|path| path.is_file()) {
+			config.views.extend(load_views(&views)?);
+		}
+
+		Ok(config)
+	}
+
+	/// Reads and parses the configuration file at `path`.
+	pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+		let contents = fs::read_to_string(path).map_err(|e| ConfigError::io(path, e))?;
+
+		let mut config: Config = if contents.trim().is_empty() {
+			Config::default()
+		} else {
+			serde_yaml::from_str(&contents).map_err(|e| ConfigError::parse(path, e))?
+		};
+
+		config.path = Some(path.to_owned());
+
+		Ok(config)
+	}
+
+	/// Checks that all values are in their valid ranges.
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		let invalid = |message: String| {
+			Err(ConfigError::Invalid {
+				path: self.path.clone(),
+				message,
+			})
+		};
+
+		if self.tick.is_zero() {
+			return invalid(String::from("`tick` must be greater than 0ms"));
+		}
+
+		if self.sampling.interval.is_zero() {
+			return invalid(String::from("`sampling.interval` must be greater than 0ms"));
+		}
+
+		let names: Vec<&str> = sampler::collectors().iter().map(|c| c.name()).collect();
+		for name in self.collectors.0.keys() {
+			if !names.contains(&name.as_str()) {
+				return invalid(format!(
+					"unknown collector `{}` in `collectors`, expected one of {}",
+					name,
+					names.join(", ")
+				));
+			}
+		}
+
+		if self.tabs.is_empty() {
+			return invalid(String::from("`tabs` must contain at least one tab"));
+		}
+
+		for (key, datapoints) in self.history.entries() {
+			if datapoints < 2 {
+				return invalid(format!("`{}` must be at least 2, got {}", key, datapoints));
+			}
+		}
+
+		if self.log_messages == 0 {
+			return invalid(String::from("`logMessages` must be greater than 0"));
+		}
+
+		if *self.network.min == 0 {
+			return invalid(String::from("`network.min` must be greater than 0B"));
+		}
+
+		Ok(())
+	}
+
+	/// Loads the theme named in this configuration.
+	///
+	/// Theme files are searched relative to the directory of the configuration file.
+	pub fn load_theme(&self) -> Result<Theme, ConfigError> {
+		let theme = match &self.theme {
+			Some(name) => Theme::load(name, self.path.as_deref().and_then(Path::parent))?,
+			None => Theme::default(),
+		};
+
+		if self.no_color {
+			Ok(theme.without_color())
+		} else {
+			Ok(theme)
+		}
+	}
+
+	/// Gets the configuration file, theme file and views file, if they exist.
+	pub fn files(&self) -> Vec<PathBuf> {
+		let dir = self.path.as_deref().and_then(Path::parent);
+		let theme = self
+			.theme
+			.as_deref()
+			.and_then(|name| Theme::path(name, dir));
+		let views = self.views_path().filter(|path| path.is_file());
+
+		self.path
+			.iter()
+			.cloned()
+			.chain(theme)
+			.chain(views)
+			.collect()
+	}
+
+	/// Gets the tabs followed by a tab for every view.
+	pub fn tabs_with_views(&self) -> Vec<Tab> {
+		let views = self.views.iter().map(|view| Tab {
+			title: Some(view.name.clone()),
+			filter: Some(view.filter.clone()),
+			..Tab::new(Screen::Processes)
+		});
+
+		self.tabs.iter().cloned().chain(views).collect()
+	}
+
+	/// Gets the views file, next to the configuration file or in the first config directory.
+	pub fn views_path(&self) -> Option<PathBuf> {
+		match self.path.as_deref().and_then(Path::parent) {
+			Some(dir) => Some(dir.join(VIEWS_FILE)),
+			None => config_dirs().next().map(|dir| dir.join(VIEWS_FILE)),
+		}
+	}
+
+	/// Searches the config directories for a configuration file.
+	fn discover() -> Option<PathBuf> {
+		config_dirs()
+			.map(|dir| dir.join(CONFIG_FILE))
+			.find(|path| path.is_file())
+	}
+}
+
+/// A named process filter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct View {
+	pub name: String,
+	pub filter: Filter,
+}
+
+/// Saves `view` to the views file of `config`, replacing a view with the same name.
+///
+/// Returns the path of the views file.
+pub fn save_view(config: &Config, view: View) -> Result<PathBuf, ConfigError> {
+	let path = config.views_path().ok_or_else(|| ConfigError::Invalid {
+		path: None,
+		message: String::from("no config directory to save views in, set $HOME"),
+	})?;
+
+	let mut views = if path.is_file() {
+		load_views(&path)?
+	} else {
+		Vec::new()
+	};
+	match views.iter_mut().find(|saved| saved.name == view.name) {
+		Some(saved) => *saved = view,
+		None => views.push(view),
+	}
+
+	let contents = serde_yaml::to_string(&views).map_err(|e| ConfigError::Invalid {
+		path: Some(path.clone()),
+		message: e.to_string(),
+	})?;
+	if let Some(dir) = path.parent() {
+		fs::create_dir_all(dir).map_err(|e| ConfigError::io(dir, e))?;
+	}
+	fs::write(&path, contents).map_err(|e| ConfigError::io(&path, e))?;
+
+	Ok(path)
+}
+
+/// Reads and parses the views file at `path`.
+fn load_views(path: &Path) -> Result<Vec<View>, ConfigError> {
+	let contents = fs::read_to_string(path).map_err(|e| ConfigError::io(path, e))?;
+	if contents.trim().is_empty() {
+		return Ok(Vec::new());
+	}
+
+	serde_yaml::from_str(&contents).map_err(|e| ConfigError::parse(path, e))
+}
+
+/// The directories searched for configuration files, `$XDG_CONFIG_HOME/tusk` before
+/// `$HOME/.config/tusk`.
+fn config_dirs() -> impl Iterator<Item = PathBuf> {
+	let xdg = env::var_os("XDG_CONFIG_HOME")
+		.filter(|dir| !dir.is_empty())
+		.map(|dir| PathBuf::from(dir).join("tusk"));
+
+	let home = env::var_os("HOME")
+		.filter(|dir| !dir.is_empty())
+		.map(|dir| PathBuf::from(dir).join(".config").join("tusk"));
+
+	[xdg, home].into_iter().flatten()
+}
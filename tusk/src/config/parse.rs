@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+/// Parses a duration like `250ms`, `1.5s` or `2m`. A number without a suffix is in milliseconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+	let s = s.trim();
+	let split = s
+		.find(|c: char| !(c.is_ascii_digit() || c == '.'))
+		.unwrap_or(s.len());
+	let (number, unit) = s.split_at(split);
+
+	let number: f64 = number
+		.parse()
+		.map_err(|_| format!("invalid duration `{}`", s))?;
+
+	let seconds = match unit.trim() {
+		"" | "ms" => number / 1000.0,
+		"s" => number,
+		"m" | "min" => number * 60.0,
+		"h" => number * 60.0 * 60.0,
+		unit => {
+			return Err(format!(
+				"unknown time unit `{}`, expected one of ms, s, m or h",
+				unit
+			))
+		},
+	};
+
+	Duration::try_from_secs_f64(seconds).map_err(|e| format!("invalid duration `{}`: {}", s, e))
+}
+
+/// (De)serializes a [`Duration`] as a string like `250ms`.
+pub mod duration {
+	use std::time::Duration;
+
+	use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+	use super::parse_duration;
+
+	pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&format!("{}ms", duration.as_millis()))
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+		let value = serde_yaml::Value::deserialize(deserializer)?;
+		match value {
+			serde_yaml::Value::String(s) => parse_duration(&s).map_err(D::Error::custom),
+			serde_yaml::Value::Number(n) => match n.as_u64() {
+				Some(millis) => Ok(Duration::from_millis(millis)),
+				None => Err(D::Error::custom(format!("invalid duration `{}`", n))),
+			},
+			_ => Err(D::Error::custom("expected a duration like `250ms`")),
+		}
+	}
+}
+
+/// (De)serializes a `memu` unit as a string like `8KB`.
+pub mod unit {
+	use std::{fmt::Display, ops::Deref, str::FromStr};
+
+	use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S, T>(unit: &T, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+		T: Deref<Target = u64>,
+	{
+		serializer.serialize_str(&format!("{}B", **unit))
+	}
+
+	pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+	where
+		D: Deserializer<'de>,
+		T: FromStr,
+		T::Err: Display,
+	{
+		let value = serde_yaml::Value::deserialize(deserializer)?;
+		let s = match value {
+			serde_yaml::Value::String(s) => s,
+			serde_yaml::Value::Number(n) => n.to_string(),
+			_ => return Err(D::Error::custom("expected an amount of data like `8KB`")),
+		};
+		s.parse().map_err(D::Error::custom)
+	}
+}
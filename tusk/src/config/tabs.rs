@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use super::{filter::Filter, layout::Node};
+use crate::terminal::Screen;
+
+/// A tab shown in the top bar.
+///
+/// In the configuration file this is either just the name of a screen,
+/// or a map with the screen and its settings. A tab with a `layout` but no `screen`
+/// is a [`Screen::Custom`] tab.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(try_from = "serde_yaml::Value")]
+#[serde(rename_all = "camelCase")]
+pub struct Tab {
+	/// The screen drawn when this tab is selected.
+	pub screen: Screen,
+	/// The title displayed in the top bar, the screens name if not set.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub title: Option<String>,
+	/// Only processes passing this filter are listed, used by the processes module.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub filter: Option<Filter>,
+	/// The modules drawn when this tab is selected.
+	pub layout: Node,
+}
+
+impl Tab {
+	/// Creates a tab for `screen` without any settings.
+	pub fn new(screen: Screen) -> Self {
+		Tab {
+			screen,
+			title: None,
+			filter: None,
+			layout: Node::builtin(screen),
+		}
+	}
+
+	/// Gets the title displayed in the top bar.
+	pub fn title(&self) -> &str {
+		match &self.title {
+			Some(title) => title,
+			None => self.screen.as_string(),
+		}
+	}
+}
+
+/// The tabs shown when none are configured.
+pub fn default_tabs() -> Vec<Tab> {
+	vec![
+		Tab::new(Screen::Default),
+		Tab::new(Screen::Processes),
+		Tab::new(Screen::Disks),
+		Tab::new(Screen::Tracked),
+	]
+}
+
+/// A tab written as a map in the configuration file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TabFull {
+	#[serde(default)]
+	screen: Option<Screen>,
+	#[serde(default)]
+	title: Option<String>,
+	#[serde(default)]
+	filter: Option<Filter>,
+	#[serde(default)]
+	layout: Option<Node>,
+}
+
+impl TryFrom<serde_yaml::Value> for Tab {
+	type Error = String;
+
+	fn try_from(value: serde_yaml::Value) -> Result<Self, Self::Error> {
+		match value {
+			serde_yaml::Value::String(screen) => Ok(Tab::new(screen.parse()?)),
+			value @ serde_yaml::Value::Mapping(_) => {
+				let full: TabFull = serde_yaml::from_value(value).map_err(|e| e.to_string())?;
+				let screen = match (full.screen, &full.layout) {
+					(Some(screen), _) => screen,
+					(None, Some(_)) => Screen::Custom,
+					(None, None) => {
+						return Err(String::from("a tab needs a `screen` or a `layout`"))
+					},
+				};
+
+				Ok(Tab {
+					screen,
+					title: full.title,
+					filter: full.filter,
+					layout: full.layout.unwrap_or_else(|| Node::builtin(screen)),
+				})
+			},
+			_ => Err(String::from(
+				"expected the name of a screen or a map with a `screen` key",
+			)),
+		}
+	}
+}
@@ -1,14 +1,33 @@
-use std::sync::Arc;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::{Arc, PoisonError, RwLock},
+};
 
 use lazy_static::lazy_static;
 use ratatui::{
-	style::{Color, Style},
+	style::{Color, Modifier, Style},
 	symbols::Marker,
 };
 use serde::{Deserialize, Serialize};
 
+use super::error::ConfigError;
+
 lazy_static! {
-	pub static ref THEME: Arc<Theme> = Arc::new(Theme::default());
+	pub static ref THEME: RwLock<Arc<Theme>> = RwLock::new(Arc::new(Theme::default()));
+}
+
+/// The names of all built-in themes.
+pub const BUILTIN_THEMES: [&str; 4] = ["dark", "light", "solarized", "high-contrast"];
+
+/// Returns the currently active theme.
+pub fn current() -> Arc<Theme> {
+	Arc::clone(&THEME.read().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Makes `theme` the active theme.
+pub fn set(theme: Theme) {
+	*THEME.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(theme);
 }
 
 #[derive(Clone, Copy)]
@@ -28,7 +47,8 @@ pub struct Theme {
 }
 
 impl Theme {
-	pub const fn new() -> Self {
+	/// The default theme, light blue on the terminals background.
+	pub const fn dark() -> Self {
 		Theme {
 			window: Style::new().fg(Color::LightBlue).bg(Color::Reset),
 			graph_style: Marker::Braille,
@@ -40,47 +60,287 @@ impl Theme {
 			tab: Style::new().fg(Color::DarkGray).bg(Color::Reset),
 			selected_tab: Style::new().fg(Color::LightBlue).bg(Color::Reset),
 			text: Style::new().fg(Color::LightBlue).bg(Color::Reset),
-			selected_text: Style::new()
-				.fg(Color::DarkGray)
-				.bg(Color::LightBlue)
-				.bg(Color::Reset),
+			selected_text: Style::new().fg(Color::DarkGray).bg(Color::LightBlue),
 			error: Style::new().fg(Color::Red).bg(Color::Reset),
 		}
 	}
+
+	/// A theme for terminals with a light background.
+	pub const fn light() -> Self {
+		Theme {
+			window: Style::new().fg(Color::Blue).bg(Color::Reset),
+			graph_style: Marker::Braille,
+			graph_1: Style::new().fg(Color::Green).bg(Color::Reset),
+			graph_2: Style::new().fg(Color::Red).bg(Color::Reset),
+			graph_3: Style::new().fg(Color::Magenta).bg(Color::Reset),
+			header: Style::new().fg(Color::Blue).bg(Color::Reset),
+			axis: Style::new().fg(Color::DarkGray).bg(Color::Reset),
+			tab: Style::new().fg(Color::Gray).bg(Color::Reset),
+			selected_tab: Style::new().fg(Color::Blue).bg(Color::Reset),
+			text: Style::new().fg(Color::Black).bg(Color::Reset),
+			selected_text: Style::new().fg(Color::White).bg(Color::Blue),
+			error: Style::new().fg(Color::Red).bg(Color::Reset),
+		}
+	}
+
+	/// The solarized dark color scheme.
+	pub const fn solarized() -> Self {
+		const BASE03: Color = Color::Rgb(0x00, 0x2b, 0x36);
+		const BASE01: Color = Color::Rgb(0x58, 0x6e, 0x75);
+		const BASE0: Color = Color::Rgb(0x83, 0x94, 0x96);
+		const YELLOW: Color = Color::Rgb(0xb5, 0x89, 0x00);
+		const RED: Color = Color::Rgb(0xdc, 0x32, 0x2f);
+		const BLUE: Color = Color::Rgb(0x26, 0x8b, 0xd2);
+		const CYAN: Color = Color::Rgb(0x2a, 0xa1, 0x98);
+		const GREEN: Color = Color::Rgb(0x85, 0x99, 0x00);
+
+		Theme {
+			window: Style::new().fg(BLUE).bg(BASE03),
+			graph_style: Marker::Braille,
+			graph_1: Style::new().fg(GREEN).bg(BASE03),
+			graph_2: Style::new().fg(RED).bg(BASE03),
+			graph_3: Style::new().fg(YELLOW).bg(BASE03),
+			header: Style::new().fg(CYAN).bg(BASE03),
+			axis: Style::new().fg(BASE01).bg(BASE03),
+			tab: Style::new().fg(BASE01).bg(BASE03),
+			selected_tab: Style::new().fg(BLUE).bg(BASE03),
+			text: Style::new().fg(BASE0).bg(BASE03),
+			selected_text: Style::new().fg(BASE03).bg(BASE0),
+			error: Style::new().fg(RED).bg(BASE03),
+		}
+	}
+
+	/// Bright colors on black, bold where it helps readability.
+	pub const fn high_contrast() -> Self {
+		Theme {
+			window: Style::new().fg(Color::White).bg(Color::Black),
+			graph_style: Marker::Block,
+			graph_1: Style::new().fg(Color::LightGreen).bg(Color::Black),
+			graph_2: Style::new().fg(Color::LightRed).bg(Color::Black),
+			graph_3: Style::new().fg(Color::LightYellow).bg(Color::Black),
+			header: Style::new()
+				.fg(Color::White)
+				.bg(Color::Black)
+				.add_modifier(Modifier::BOLD),
+			axis: Style::new().fg(Color::White).bg(Color::Black),
+			tab: Style::new().fg(Color::Gray).bg(Color::Black),
+			selected_tab: Style::new()
+				.fg(Color::Black)
+				.bg(Color::White)
+				.add_modifier(Modifier::BOLD),
+			text: Style::new().fg(Color::White).bg(Color::Black),
+			selected_text: Style::new().fg(Color::Black).bg(Color::White),
+			error: Style::new()
+				.fg(Color::LightRed)
+				.bg(Color::Black)
+				.add_modifier(Modifier::BOLD),
+		}
+	}
+
+	/// Removes all colors from this theme.
+	///
+	/// Selected tabs and text are reversed instead, so they can still be told apart.
+	pub fn without_color(self) -> Self {
+		let strip = |style: Style| Style {
+			fg: None,
+			bg: None,
+			..style
+		};
+
+		Theme {
+			window: strip(self.window),
+			graph_style: self.graph_style,
+			graph_1: strip(self.graph_1),
+			graph_2: strip(self.graph_2),
+			graph_3: strip(self.graph_3),
+			header: strip(self.header).add_modifier(Modifier::BOLD),
+			axis: strip(self.axis),
+			tab: strip(self.tab),
+			selected_tab: strip(self.selected_tab).add_modifier(Modifier::REVERSED),
+			text: strip(self.text),
+			selected_text: strip(self.selected_text).add_modifier(Modifier::REVERSED),
+			error: strip(self.error).add_modifier(Modifier::BOLD),
+		}
+	}
+
+	/// Gets a built-in theme by name.
+	pub fn builtin(name: &str) -> Option<Self> {
+		match name {
+			"dark" => Some(Self::dark()),
+			"light" => Some(Self::light()),
+			"solarized" => Some(Self::solarized()),
+			"high-contrast" => Some(Self::high_contrast()),
+			_ => None,
+		}
+	}
+
+	/// Loads a theme, `name` is either the name of a built-in theme or the path to a theme file.
+	///
+	/// Relative paths are resolved relative to `dir`.
+	pub fn load(name: &str, dir: Option<&Path>) -> Result<Self, ConfigError> {
+		if let Some(theme) = Self::builtin(name) {
+			return Ok(theme);
+		}
+
+		let path =
+			Self::path(name, dir).ok_or_else(|| ConfigError::UnknownTheme(name.to_owned()))?;
+
+		if !path.is_file() {
+			return Err(ConfigError::UnknownTheme(name.to_owned()));
+		}
+
+		let contents = fs::read_to_string(&path).map_err(|e| ConfigError::io(&path, e))?;
+
+		let wrapper: WrapperTheme = if contents.trim().is_empty() {
+			WrapperTheme::default()
+		} else {
+			serde_yaml::from_str(&contents).map_err(|e| ConfigError::parse(&path, e))?
+		};
+
+		let mut theme = match &wrapper.base {
+			Some(base) => {
+				Self::builtin(base).ok_or_else(|| ConfigError::UnknownTheme(base.clone()))?
+			},
+			None => Self::default(),
+		};
+		wrapper.apply(&mut theme);
+
+		Ok(theme)
+	}
+
+	/// Gets the path of the theme file `name` relative to `dir`, `None` for built-in themes.
+	pub fn path(name: &str, dir: Option<&Path>) -> Option<PathBuf> {
+		if Self::builtin(name).is_some() {
+			return None;
+		}
+
+		Some(match dir {
+			Some(dir) => dir.join(name),
+			None => Path::new(name).to_owned(),
+		})
+	}
+}
+
+impl Default for Theme {
+	fn default() -> Self {
+		Self::dark()
+	}
 }
 
-#[derive(Serialize, Deserialize)]
+/// The markers a graph can be drawn with.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 #[serde(rename_all = "camelCase")]
+enum GraphStyle {
+	Braille,
+	Dot,
+	Block,
+	Bar,
+}
+
+impl From<GraphStyle> for Marker {
+	fn from(value: GraphStyle) -> Self {
+		match value {
+			GraphStyle::Braille => Marker::Braille,
+			GraphStyle::Dot => Marker::Dot,
+			GraphStyle::Block => Marker::Block,
+			GraphStyle::Bar => Marker::Bar,
+		}
+	}
+}
+
+/// The theme as it is written in a theme file.
+///
+/// Every key is optional, missing keys are taken from the `base` theme.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
 struct WrapperTheme {
-	borders: Color,
-	background: Color,
-	graph: Color,
-	tab_bg: Color,
-	tab_fg: Color,
-	selected_tab_fg: Color,
-	selected_tab_bg: Color,
-	selected_text_bg: Color,
-	selected_text_fg: Color,
+	/// The built-in theme this theme is based on.
+	base: Option<String>,
+	graph_style: Option<GraphStyle>,
+	#[serde(with = "color_to_tui::optional")]
+	background: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	borders: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	graph_1: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	graph_2: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	graph_3: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	header: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	axis: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	text: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	error: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	tab_bg: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	tab_fg: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	selected_tab_fg: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	selected_tab_bg: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	selected_text_bg: Option<Color>,
+	#[serde(with = "color_to_tui::optional")]
+	selected_text_fg: Option<Color>,
 }
 
-impl Default for Theme {
-	fn default() -> Self {
-		Theme {
-			window: Style::default().fg(Color::LightBlue).bg(Color::Reset),
-			graph_style: Marker::Braille,
-			graph_1: Style::default().fg(Color::Green).bg(Color::Reset),
-			graph_2: Style::default().fg(Color::Red).bg(Color::Reset),
-			graph_3: Style::default().fg(Color::Yellow).bg(Color::Reset),
-			header: Style::default().fg(Color::LightBlue).bg(Color::Reset),
-			axis: Style::default().fg(Color::LightBlue).bg(Color::Reset),
-			tab: Style::default().fg(Color::DarkGray).bg(Color::Reset),
-			selected_tab: Style::default().fg(Color::LightBlue).bg(Color::Reset),
-			text: Style::default().fg(Color::LightBlue).bg(Color::Reset),
-			selected_text: Style::default()
-				.fg(Color::DarkGray)
-				.bg(Color::LightBlue)
-				.bg(Color::Reset),
-			error: Style::default().fg(Color::Red).bg(Color::Reset),
+impl WrapperTheme {
+	/// Overrides every style of `theme` that is set in this theme.
+	fn apply(&self, theme: &mut Theme) {
+		if let Some(graph_style) = self.graph_style {
+			theme.graph_style = graph_style.into();
+		}
+
+		if let Some(background) = self.background {
+			for style in [
+				&mut theme.window,
+				&mut theme.graph_1,
+				&mut theme.graph_2,
+				&mut theme.graph_3,
+				&mut theme.header,
+				&mut theme.axis,
+				&mut theme.tab,
+				&mut theme.selected_tab,
+				&mut theme.text,
+				&mut theme.error,
+			] {
+				*style = style.bg(background);
+			}
+		}
+
+		let foregrounds = [
+			(self.borders, &mut theme.window),
+			(self.graph_1, &mut theme.graph_1),
+			(self.graph_2, &mut theme.graph_2),
+			(self.graph_3, &mut theme.graph_3),
+			(self.header, &mut theme.header),
+			(self.axis, &mut theme.axis),
+			(self.text, &mut theme.text),
+			(self.error, &mut theme.error),
+			(self.tab_fg, &mut theme.tab),
+			(self.selected_tab_fg, &mut theme.selected_tab),
+			(self.selected_text_fg, &mut theme.selected_text),
+		];
+		for (color, style) in foregrounds {
+			if let Some(color) = color {
+				*style = style.fg(color);
+			}
+		}
+
+		let backgrounds = [
+			(self.tab_bg, &mut theme.tab),
+			(self.selected_tab_bg, &mut theme.selected_tab),
+			(self.selected_text_bg, &mut theme.selected_text),
+		];
+		for (color, style) in backgrounds {
+			if let Some(color) = color {
+				*style = style.bg(color);
+			}
 		}
 	}
 }
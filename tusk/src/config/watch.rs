@@ -0,0 +1,79 @@
+use std::{
+	ffi::OsString,
+	io,
+	path::{Path, PathBuf},
+};
+
+use inotify::{Inotify, WatchMask};
+
+/// Watches files for changes using inotify.
+///
+/// The directories containing the files are watched instead of the files themselves,
+/// since most editors save by replacing the file.
+pub struct Watcher {
+	inotify: Inotify,
+	/// The names of the watched files.
+	files: Vec<OsString>,
+	buffer: [u8; 4096],
+}
+
+impl Watcher {
+	/// Starts watching `paths`.
+	pub fn new<'a, I>(paths: I) -> io::Result<Self>
+	where
+		I: IntoIterator<Item = &'a Path>,
+	{
+		let inotify = Inotify::init()?;
+		let mut files = Vec::new();
+		let mut dirs: Vec<PathBuf> = Vec::new();
+
+		for path in paths {
+			let (Some(dir), Some(file)) = (path.parent(), path.file_name()) else {
+				continue;
+			};
+			let dir = if dir.as_os_str().is_empty() {
+				Path::new(".")
+			} else {
+				dir
+			};
+
+			files.push(file.to_owned());
+
+			if !dirs.iter().any(|watched| watched == dir) {
+				inotify.watches().add(
+					dir,
+					WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE,
+				)?;
+				dirs.push(dir.to_owned());
+			}
+		}
+
+		Ok(Watcher {
+			inotify,
+			files,
+			buffer: [0; 4096],
+		})
+	}
+
+	/// Returns true if any watched file changed since the last call, without blocking.
+	pub fn changed(&mut self) -> bool {
+		let mut changed = false;
+
+		while let Ok(events) = self.inotify.read_events(&mut self.buffer) {
+			let mut any = false;
+
+			for event in events {
+				any = true;
+				changed |= event
+					.name
+					.is_some_and(|name| self.files.iter().any(|file| file == name));
+			}
+
+			if !any {
+				break;
+			}
+		}
+
+		changed
+	}
+}
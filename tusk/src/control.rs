@@ -0,0 +1,471 @@
+use std::{fmt::Display, fs, io, mem, str::FromStr};
+
+/// A signal that can be sent to a process.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Signal {
+	Term,
+	Kill,
+	Int,
+	Hup,
+	Stop,
+	Cont,
+	Usr1,
+	Usr2,
+}
+
+impl Signal {
+	/// Every signal, in the order they are offered.
+	pub const ALL: [Signal; 8] = [
+		Self::Term,
+		Self::Kill,
+		Self::Int,
+		Self::Hup,
+		Self::Stop,
+		Self::Cont,
+		Self::Usr1,
+		Self::Usr2,
+	];
+
+	/// Gets the name of the signal, like `SIGTERM`.
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Term => "SIGTERM",
+			Self::Kill => "SIGKILL",
+			Self::Int => "SIGINT",
+			Self::Hup => "SIGHUP",
+			Self::Stop => "SIGSTOP",
+			Self::Cont => "SIGCONT",
+			Self::Usr1 => "SIGUSR1",
+			Self::Usr2 => "SIGUSR2",
+		}
+	}
+
+	/// Describes what the process usually does when receiving the signal.
+	pub fn description(self) -> &'static str {
+		match self {
+			Self::Term => "ask to terminate",
+			Self::Kill => "kill immediately",
+			Self::Int => "interrupt, like ctrl+c",
+			Self::Hup => "hang up, often reloads",
+			Self::Stop => "pause",
+			Self::Cont => "resume after a pause",
+			Self::Usr1 => "user defined",
+			Self::Usr2 => "user defined",
+		}
+	}
+
+	fn number(self) -> libc::c_int {
+		match self {
+			Self::Term => libc::SIGTERM,
+			Self::Kill => libc::SIGKILL,
+			Self::Int => libc::SIGINT,
+			Self::Hup => libc::SIGHUP,
+			Self::Stop => libc::SIGSTOP,
+			Self::Cont => libc::SIGCONT,
+			Self::Usr1 => libc::SIGUSR1,
+			Self::Usr2 => libc::SIGUSR2,
+		}
+	}
+}
+
+/// The scheduling class of the io of a process, see `ioprio_set(2)`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IoClass {
+	/// Derived from the nice value by the kernel.
+	None,
+	Realtime,
+	BestEffort,
+	/// Only gets disk time when no other process needs it.
+	Idle,
+}
+
+/// The io priority of a process, a class and a level from 0 (highest) to 7 within the class.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IoPriority {
+	pub class: IoClass,
+	pub level: u8,
+}
+
+/// The bits the class is shifted by in an io priority.
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+/// `IOPRIO_WHO_PROCESS`, io priorities are set per process.
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+
+impl IoPriority {
+	fn from_raw(raw: libc::c_int) -> Self {
+		let class = match raw >> IOPRIO_CLASS_SHIFT {
+			1 => IoClass::Realtime,
+			2 => IoClass::BestEffort,
+			3 => IoClass::Idle,
+			_ => IoClass::None,
+		};
+
+		IoPriority {
+			class,
+			level: (raw & ((1 << IOPRIO_CLASS_SHIFT) - 1)) as u8,
+		}
+	}
+
+	fn raw(self) -> libc::c_int {
+		let class = match self.class {
+			IoClass::None => 0,
+			IoClass::Realtime => 1,
+			IoClass::BestEffort => 2,
+			IoClass::Idle => 3,
+		};
+		(class << IOPRIO_CLASS_SHIFT) | self.level as libc::c_int
+	}
+}
+
+impl FromStr for IoPriority {
+	type Err = String;
+
+	/// Parses an io priority like `best-effort 4`, `rt 0` or `idle`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut parts = s.split_whitespace();
+		let class = match parts.next().map(str::to_ascii_lowercase).as_deref() {
+			Some("none") => IoClass::None,
+			Some("realtime" | "rt") => IoClass::Realtime,
+			Some("best-effort" | "be") => IoClass::BestEffort,
+			Some("idle") => IoClass::Idle,
+			Some(class) => {
+				return Err(format!(
+					"unknown io class `{}`, expected one of realtime, best-effort, idle or none",
+					class
+				))
+			},
+			None => return Err(String::from("expected an io class like `best-effort 4`")),
+		};
+
+		let level = match (class, parts.next()) {
+			(IoClass::Idle | IoClass::None, None) => 0,
+			(IoClass::Idle | IoClass::None, Some(_)) => {
+				return Err(format!(
+					"the {} class has no levels",
+					IoPriority { class, level: 0 }
+				))
+			},
+			(_, None) => 4,
+			(_, Some(level)) => match level.parse() {
+				Ok(level @ 0..=7) => level,
+				_ => return Err(format!("invalid level `{}`, expected 0 to 7", level)),
+			},
+		};
+		if let Some(part) = parts.next() {
+			return Err(format!("unexpected `{}` after the level", part));
+		}
+
+		Ok(IoPriority { class, level })
+	}
+}
+
+impl Display for IoPriority {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.class {
+			IoClass::None => write!(f, "none"),
+			IoClass::Realtime => write!(f, "realtime {}", self.level),
+			IoClass::BestEffort => write!(f, "best-effort {}", self.level),
+			IoClass::Idle => write!(f, "idle"),
+		}
+	}
+}
+
+/// The cores a process may run on, sorted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Affinity(pub Vec<usize>);
+
+impl FromStr for Affinity {
+	type Err = String;
+
+	/// Parses a list of cores like `0-3,6`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid =
+			|part: &str| format!("invalid cores `{}`, expected a list like `0-3,6`", part);
+		let mut cores = Vec::new();
+
+		for part in s.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+			let (first, last) = part.split_once('-').unwrap_or((part, part));
+			let first: usize = first.trim().parse().map_err(|_| invalid(part))?;
+			let last: usize = last.trim().parse().map_err(|_| invalid(part))?;
+			if first > last || last >= libc::CPU_SETSIZE as usize {
+				return Err(invalid(part));
+			}
+			cores.extend(first..=last);
+		}
+
+		if cores.is_empty() {
+			return Err(String::from("a process needs at least one core"));
+		}
+		cores.sort_unstable();
+		cores.dedup();
+
+		Ok(Affinity(cores))
+	}
+}
+
+impl Display for Affinity {
+	/// Writes the cores as a list of ranges like `0-3,6`.
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut cores = self.0.iter().copied().peekable();
+		let mut first = true;
+
+		while let Some(start) = cores.next() {
+			let mut end = start;
+			while let Some(next) = cores.next_if_eq(&(end + 1)) {
+				end = next;
+			}
+
+			if !first {
+				write!(f, ",")?;
+			}
+			first = false;
+
+			if start == end {
+				write!(f, "{}", start)?;
+			} else {
+				write!(f, "{}-{}", start, end)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// A change made to a process.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Operation {
+	Signal(Signal),
+	/// Sets the nice value, from -20 (highest priority) to 19.
+	Nice(i32),
+	IoPriority(IoPriority),
+	Affinity(Affinity),
+}
+
+impl Operation {
+	/// Applies this change to the process `pid`.
+	///
+	/// Nice values, io priorities and affinities belong to threads, so they are changed for
+	/// every thread of the process. Threads started afterwards inherit them from their creator.
+	pub fn apply(&self, pid: u32) -> io::Result<()> {
+		if let Self::Signal(_) = self {
+			return self.apply_to(pid);
+		}
+
+		for tid in threads(pid)? {
+			match self.apply_to(tid) {
+				// A thread may exit while the others are changed.
+				Err(e) if tid != pid && e.raw_os_error() == Some(libc::ESRCH) => (),
+				result => result?,
+			}
+		}
+		Ok(())
+	}
+
+	/// Applies this change to the process or thread `pid` alone.
+	fn apply_to(&self, pid: u32) -> io::Result<()> {
+		let pid = pid as libc::pid_t;
+		// SAFETY: The calls only read the memory passed to them, which outlives them.
+		let result = unsafe {
+			match self {
+				Self::Signal(signal) => libc::kill(pid, signal.number()),
+				Self::Nice(nice) => libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, *nice),
+				Self::IoPriority(priority) => libc::syscall(
+					libc::SYS_ioprio_set,
+					IOPRIO_WHO_PROCESS,
+					pid,
+					priority.raw(),
+				) as libc::c_int,
+				Self::Affinity(Affinity(cores)) => {
+					let mut set: libc::cpu_set_t = mem::zeroed();
+					for &core in cores {
+						libc::CPU_SET(core, &mut set);
+					}
+					libc::sched_setaffinity(pid, mem::size_of::<libc::cpu_set_t>(), &set)
+				},
+			}
+		};
+
+		if result == -1 {
+			Err(io::Error::last_os_error())
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Describes the change made to `process`, like `send SIGTERM to bash (42)`,
+	/// or `sent SIGTERM to bash (42)` once it is `done`.
+	pub fn describe(&self, process: &str, done: bool) -> String {
+		match self {
+			Self::Signal(signal) => format!(
+				"{} {} to {}",
+				if done { "sent" } else { "send" },
+				signal.name(),
+				process
+			),
+			Self::Nice(nice) => format!("set the nice value of {} to {}", process, nice),
+			Self::IoPriority(priority) => {
+				format!("set the io priority of {} to {}", process, priority)
+			},
+			Self::Affinity(affinity) => {
+				format!("set the cpu affinity of {} to {}", process, affinity)
+			},
+		}
+	}
+}
+
+/// Lists the threads of the process `pid`, from `/proc/<pid>/task`.
+fn threads(pid: u32) -> io::Result<Vec<u32>> {
+	match fs::read_dir(format!("/proc/{}/task", pid)) {
+		Ok(entries) => Ok(entries
+			.filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+			.collect()),
+		// Reported like the calls on a missing process would.
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {
+			Err(io::Error::from_raw_os_error(libc::ESRCH))
+		},
+		Err(e) => Err(e),
+	}
+}
+
+/// Reads the nice value of the process `pid` from `/proc/<pid>/stat`.
+pub fn read_nice(pid: u32) -> io::Result<i32> {
+	let stat = fs::read_to_string(format!("/proc/{}/stat", pid))?;
+	parse_nice(&stat).ok_or_else(|| io::Error::other("can't parse the nice value"))
+}
+
+/// Gets the nice value from the contents of a `/proc/<pid>/stat` file.
+fn parse_nice(stat: &str) -> Option<i32> {
+	// The name in parentheses may contain spaces, the nice value is the 17th field after it.
+	let (_, fields) = stat.rsplit_once(')')?;
+	fields.split_whitespace().nth(16)?.parse().ok()
+}
+
+/// Reads the io priority of the process `pid`.
+pub fn read_io_priority(pid: u32) -> io::Result<IoPriority> {
+	// SAFETY: `ioprio_get` takes no pointers.
+	let raw =
+		unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid as libc::pid_t) };
+
+	if raw == -1 {
+		Err(io::Error::last_os_error())
+	} else {
+		Ok(IoPriority::from_raw(raw as libc::c_int))
+	}
+}
+
+/// Reads the cores the process `pid` may run on.
+pub fn read_affinity(pid: u32) -> io::Result<Affinity> {
+	// SAFETY: The set is a plain bitmask written by `sched_getaffinity`, zeroed is valid.
+	unsafe {
+		let mut set: libc::cpu_set_t = mem::zeroed();
+		if libc::sched_getaffinity(
+			pid as libc::pid_t,
+			mem::size_of::<libc::cpu_set_t>(),
+			&mut set,
+		) == -1
+		{
+			return Err(io::Error::last_os_error());
+		}
+
+		Ok(Affinity(
+			(0..libc::CPU_SETSIZE as usize)
+				.filter(|&core| libc::CPU_ISSET(core, &set))
+				.collect(),
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn affinity() {
+		let affinity: Affinity = "0-3,6".parse().unwrap();
+		assert_eq!(affinity, Affinity(vec![0, 1, 2, 3, 6]));
+		assert_eq!(affinity.to_string(), "0-3,6");
+
+		let affinity: Affinity = " 7, 2-3 ,1,3 ".parse().unwrap();
+		assert_eq!(affinity, Affinity(vec![1, 2, 3, 7]));
+		assert_eq!(affinity.to_string(), "1-3,7");
+		assert_eq!(affinity.to_string().parse(), Ok(affinity));
+
+		assert_eq!(
+			"3-1".parse::<Affinity>(),
+			Err(String::from(
+				"invalid cores `3-1`, expected a list like `0-3,6`"
+			))
+		);
+		assert_eq!(
+			"0,a".parse::<Affinity>(),
+			Err(String::from(
+				"invalid cores `a`, expected a list like `0-3,6`"
+			))
+		);
+		assert!("0-100000".parse::<Affinity>().is_err());
+		assert_eq!(
+			"".parse::<Affinity>(),
+			Err(String::from("a process needs at least one core"))
+		);
+		assert_eq!(
+			" , ".parse::<Affinity>(),
+			Err(String::from("a process needs at least one core"))
+		);
+	}
+
+	#[test]
+	fn io_priority() {
+		let parse = |s: &str| s.parse::<IoPriority>();
+
+		assert_eq!(
+			parse("rt 0"),
+			Ok(IoPriority {
+				class: IoClass::Realtime,
+				level: 0
+			})
+		);
+		assert_eq!(
+			parse("Best-Effort"),
+			Ok(IoPriority {
+				class: IoClass::BestEffort,
+				level: 4
+			})
+		);
+		for priority in ["realtime 7", "best-effort 2", "idle", "none"] {
+			assert_eq!(parse(priority).unwrap().to_string(), priority);
+		}
+
+		let priority = parse("be 6").unwrap();
+		assert_eq!(IoPriority::from_raw(priority.raw()), priority);
+
+		assert_eq!(
+			parse("idle 3"),
+			Err(String::from("the idle class has no levels"))
+		);
+		assert_eq!(
+			parse("none 0"),
+			Err(String::from("the none class has no levels"))
+		);
+		assert_eq!(
+			parse("be 8"),
+			Err(String::from("invalid level `8`, expected 0 to 7"))
+		);
+		assert_eq!(
+			parse("be 1 2"),
+			Err(String::from("unexpected `2` after the level"))
+		);
+		assert!(parse("fast").is_err());
+		assert!(parse("").is_err());
+	}
+
+	#[test]
+	fn nice() {
+		let stat = "42 (a (weird) name) S 1 42 42 0 -1 4194560 100 0 0 0 1 2 0 0 20 -5 1 0 100 \
+		            1000 10";
+		assert_eq!(parse_nice(stat), Some(-5));
+		assert_eq!(parse_nice("42 (short) S 1 42"), None);
+		assert_eq!(parse_nice(""), None);
+
+		assert!(read_nice(std::process::id()).is_ok());
+	}
+}
@@ -0,0 +1,412 @@
+use std::{
+	io::{self, Write},
+	thread,
+	time::{Duration, Instant},
+};
+
+use memu::units::Byte;
+use serde::Serialize;
+use sysinfo::{System, SystemExt};
+
+use crate::{
+	cli::{Command, Format},
+	config,
+	sampler::{self, Pressure, Sampler},
+	terminal::App,
+};
+
+/// A single sample of all metrics.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot {
+	cpu: String,
+	frequency_mhz: u64,
+	cpu_usage: f32,
+	memory: Option<MemorySnapshot>,
+	disk_read_kbs: f64,
+	disk_written_kbs: f64,
+	network_in_kbs: f64,
+	network_out_kbs: f64,
+	processes: usize,
+	system: Option<SystemSnapshot>,
+	pressure: PressureSnapshot,
+	devices: Vec<DeviceSnapshot>,
+	filesystems: Vec<FilesystemSnapshot>,
+	sensors: Vec<SensorSnapshot>,
+	tracked: Option<TrackedSnapshot>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MemorySnapshot {
+	total_mb: f64,
+	used_mb: f64,
+	available_mb: f64,
+	swap_total_mb: f64,
+	swap_used_mb: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemSnapshot {
+	load1: f64,
+	load5: f64,
+	load15: f64,
+	uptime_s: u64,
+	users: usize,
+	threads: usize,
+}
+
+/// The share of time at least one task was stalled over the last 10 seconds in percent, `None`
+/// if the kernel doesn't expose it.
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct PressureSnapshot {
+	cpu: Option<f32>,
+	memory: Option<f32>,
+	io: Option<f32>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceSnapshot {
+	name: String,
+	read_kbs: f64,
+	written_kbs: f64,
+	utilization: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FilesystemSnapshot {
+	mount_point: String,
+	total_mb: f64,
+	used_mb: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SensorSnapshot {
+	label: String,
+	temperature: f32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrackedSnapshot {
+	pid: u32,
+	name: String,
+	cpu_usage: f32,
+	memory_mb: f64,
+}
+
+/// The header of the csv lines printed by [`record`].
+const RECORD_HEADER: &str = "time_ms,cpu_usage,frequency_mhz,memory_used_mb,swap_used_mb,disk_read_kbs,disk_written_kbs,network_in_kbs,network_out_kbs,processes,load1,cpu_pressure,memory_pressure,io_pressure,tracked_pid,tracked_cpu_usage,tracked_memory_mb";
+
+impl Snapshot {
+	/// Takes the newest datapoints of `app`.
+	fn new(app: &App) -> Self {
+		let kilo_bytes =
+			|bytes: Option<&Byte>| bytes.map_or(0.0, |bytes| bytes.as_kilo_byte().as_f64());
+		let mega_bytes = |bytes: Byte| bytes.as_mega_byte().as_f64();
+		let pressure = app.pressure.back();
+		let some = |pressure: Option<&Pressure>| pressure.map(|pressure| pressure.some.avg10);
+
+		Snapshot {
+			cpu: app.cpu_name.clone(),
+			frequency_mhz: app.cpu_frequency,
+			cpu_usage: app.cpu_usage.back().copied().unwrap_or_default(),
+			memory: app.memory.back().map(|memory| MemorySnapshot {
+				total_mb: mega_bytes(memory.total),
+				used_mb: mega_bytes(memory.used),
+				available_mb: mega_bytes(memory.available),
+				swap_total_mb: mega_bytes(memory.swap_total),
+				swap_used_mb: mega_bytes(memory.swap_used),
+			}),
+			disk_read_kbs: kilo_bytes(app.disk_read.back()),
+			disk_written_kbs: kilo_bytes(app.disk_written.back()),
+			network_in_kbs: kilo_bytes(app.network_in.back()),
+			network_out_kbs: kilo_bytes(app.network_out.back()),
+			processes: app.processes.len(),
+			system: app.system.as_ref().map(|system| SystemSnapshot {
+				load1: system.load.one,
+				load5: system.load.five,
+				load15: system.load.fifteen,
+				uptime_s: system.uptime.as_secs(),
+				users: system.users,
+				threads: system.threads,
+			}),
+			pressure: PressureSnapshot {
+				cpu: some(pressure.and_then(|pressure| pressure.cpu.as_ref())),
+				memory: some(pressure.and_then(|pressure| pressure.memory.as_ref())),
+				io: some(pressure.and_then(|pressure| pressure.io.as_ref())),
+			},
+			devices: app
+				.devices
+				.iter()
+				.map(|device| DeviceSnapshot {
+					name: device.name.clone(),
+					read_kbs: device.read.as_kilo_byte().as_f64(),
+					written_kbs: device.written.as_kilo_byte().as_f64(),
+					utilization: device.utilization,
+				})
+				.collect(),
+			filesystems: app
+				.filesystems
+				.iter()
+				.map(|filesystem| FilesystemSnapshot {
+					mount_point: filesystem.mount_point.display().to_string(),
+					total_mb: mega_bytes(filesystem.total),
+					used_mb: mega_bytes(filesystem.used),
+				})
+				.collect(),
+			sensors: app
+				.sensors
+				.iter()
+				.filter_map(|sensor| {
+					Some(SensorSnapshot {
+						label: sensor.label.clone(),
+						temperature: sensor.samples.back()?.temperature,
+					})
+				})
+				.collect(),
+			tracked: app.tracked.as_ref().map(|tracked| TrackedSnapshot {
+				pid: tracked.pid,
+				name: tracked.name.clone(),
+				cpu_usage: tracked.cpu_usage.back().copied().unwrap_or_default(),
+				memory_mb: tracked.memory.back().map_or(0.0, |mb| mb.as_f64()),
+			}),
+		}
+	}
+
+	/// Writes the values of this snapshot as a csv line, see [`RECORD_HEADER`].
+	fn write_csv(&self, out: &mut impl Write, time: Duration) -> io::Result<()> {
+		let optional = |value: Option<f32>| value.map(|value| format!("{:.2}", value));
+		let (pid, cpu_usage, memory) = match &self.tracked {
+			Some(tracked) => (
+				tracked.pid.to_string(),
+				format!("{:.2}", tracked.cpu_usage),
+				format!("{:.2}", tracked.memory_mb),
+			),
+			None => Default::default(),
+		};
+
+		writeln!(
+			out,
+			"{},{:.2},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{},{},{},{},{},{},{},{}",
+			time.as_millis(),
+			self.cpu_usage,
+			self.frequency_mhz,
+			self.memory.as_ref().map_or(0.0, |memory| memory.used_mb),
+			self.memory
+				.as_ref()
+				.map_or(0.0, |memory| memory.swap_used_mb),
+			self.disk_read_kbs,
+			self.disk_written_kbs,
+			self.network_in_kbs,
+			self.network_out_kbs,
+			self.processes,
+			self.system
+				.as_ref()
+				.map(|system| format!("{:.2}", system.load1))
+				.unwrap_or_default(),
+			optional(self.pressure.cpu).unwrap_or_default(),
+			optional(self.pressure.memory).unwrap_or_default(),
+			optional(self.pressure.io).unwrap_or_default(),
+			pid,
+			cpu_usage,
+			memory
+		)
+	}
+}
+
+/// Runs a non-interactive command on `app`.
+pub fn run(command: &Command, app: &mut App) -> io::Result<()> {
+	let result = match command {
+		Command::Snapshot { format } => snapshot(app, *format),
+		Command::Record { samples } => record(app, *samples),
+		Command::Collectors => collectors(),
+	};
+
+	// Stop quietly when the output is closed, for example by `head`.
+	match result {
+		Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+		result => result,
+	}
+}
+
+/// Prints one sample of every metric.
+fn snapshot(app: &mut App, format: Format) -> io::Result<()> {
+	let mut sampler = start_sampler(app)?;
+	// Cpu usage is computed between two samples, so the first one is meaningless.
+	thread::sleep(interval());
+	take_sample(app, &mut sampler);
+
+	let snapshot = Snapshot::new(app);
+	let mut stdout = io::stdout().lock();
+
+	match format {
+		Format::Yaml => {
+			let yaml = serde_yaml::to_string(&snapshot).map_err(io::Error::other)?;
+			write!(stdout, "{}", yaml)
+		},
+		Format::Text => write_text(&mut stdout, &snapshot),
+	}
+}
+
+/// Writes `snapshot` for humans, one metric per line.
+fn write_text(out: &mut impl Write, snapshot: &Snapshot) -> io::Result<()> {
+	writeln!(out, "Cpu          {}", snapshot.cpu)?;
+	writeln!(out, "Frequency    {}MHz", snapshot.frequency_mhz)?;
+	writeln!(out, "Cpu Usage    {:.2}%", snapshot.cpu_usage)?;
+	if let Some(memory) = &snapshot.memory {
+		writeln!(
+			out,
+			"Memory       {:.2}MB of {:.2}MB, {:.2}MB available",
+			memory.used_mb, memory.total_mb, memory.available_mb
+		)?;
+		writeln!(
+			out,
+			"Swap         {:.2}MB of {:.2}MB",
+			memory.swap_used_mb, memory.swap_total_mb
+		)?;
+	}
+	writeln!(out, "Disk Read    {:.2}KB/s", snapshot.disk_read_kbs)?;
+	writeln!(out, "Disk Written {:.2}KB/s", snapshot.disk_written_kbs)?;
+	writeln!(out, "Network In   {:.2}KB/s", snapshot.network_in_kbs)?;
+	writeln!(out, "Network Out  {:.2}KB/s", snapshot.network_out_kbs)?;
+	writeln!(out, "Processes    {}", snapshot.processes)?;
+	if let Some(system) = &snapshot.system {
+		writeln!(
+			out,
+			"Load         {:.2} {:.2} {:.2}",
+			system.load1, system.load5, system.load15
+		)?;
+		writeln!(
+			out,
+			"Uptime       {}s, {} users, {} threads",
+			system.uptime_s, system.users, system.threads
+		)?;
+	}
+	for (name, pressure) in [
+		("Cpu", snapshot.pressure.cpu),
+		("Memory", snapshot.pressure.memory),
+		("Io", snapshot.pressure.io),
+	] {
+		if let Some(pressure) = pressure {
+			writeln!(out, "{:<13}{:.2}%", format!("{} Stall", name), pressure)?;
+		}
+	}
+	for device in &snapshot.devices {
+		writeln!(
+			out,
+			"Device       {} {:.2}KB/s read {:.2}KB/s written {:.0}% busy",
+			device.name, device.read_kbs, device.written_kbs, device.utilization
+		)?;
+	}
+	for filesystem in &snapshot.filesystems {
+		writeln!(
+			out,
+			"Filesystem   {} {:.2}MB of {:.2}MB",
+			filesystem.mount_point, filesystem.used_mb, filesystem.total_mb
+		)?;
+	}
+	for sensor in &snapshot.sensors {
+		writeln!(
+			out,
+			"Sensor       {} {:.1}°C",
+			sensor.label, sensor.temperature
+		)?;
+	}
+	if let Some(tracked) = &snapshot.tracked {
+		writeln!(
+			out,
+			"Tracked      {} {} {:.2}% {:.2}MB",
+			tracked.pid, tracked.name, tracked.cpu_usage, tracked.memory_mb
+		)?;
+	}
+	Ok(())
+}
+
+/// Prints a csv line every sample, stops after `samples` lines if given.
+///
+/// Block devices, filesystems and sensors differ between machines and aren't recorded, a
+/// snapshot lists them.
+fn record(app: &mut App, samples: Option<usize>) -> io::Result<()> {
+	let mut sampler = start_sampler(app)?;
+	let start = Instant::now();
+	let interval = interval();
+	let mut stdout = io::stdout().lock();
+
+	writeln!(stdout, "{}", RECORD_HEADER)?;
+
+	let mut sample = 0;
+	while samples.is_none_or(|samples| sample < samples) {
+		let tick_start = Instant::now();
+		thread::sleep(interval);
+		take_sample(app, &mut sampler);
+
+		Snapshot::new(app).write_csv(&mut stdout, tick_start - start)?;
+		stdout.flush()?;
+
+		sample += 1;
+	}
+
+	Ok(())
+}
+
+/// Prints every collector and its metrics.
+fn collectors() -> io::Result<()> {
+	let config = config::current();
+	let mut stdout = io::stdout().lock();
+
+	for collector in sampler::collectors() {
+		let state = if config.collectors.is_enabled(collector.name()) {
+			"enabled"
+		} else {
+			"disabled"
+		};
+		writeln!(stdout, "{} ({})", collector.name(), state)?;
+
+		for metric in collector.metrics() {
+			writeln!(
+				stdout,
+				"  {:<20} {:<6} {}",
+				metric.name, metric.unit, metric.description
+			)?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Creates a sampler and takes the first sample.
+///
+/// Fails if the process tracked by pid doesn't exist, a process tracked by name may still start.
+fn start_sampler(app: &mut App) -> io::Result<Sampler> {
+	let mut sampler = Sampler::new();
+	let pid = app.tracked_pid();
+	take_sample(app, &mut sampler);
+
+	match (pid, &app.tracked) {
+		(Some(pid), None) => Err(io::Error::new(
+			io::ErrorKind::NotFound,
+			format!("there is no process with the pid {}", pid),
+		)),
+		_ => Ok(sampler),
+	}
+}
+
+/// Takes a sample, following the tracked process once it is found by name.
+fn take_sample(app: &mut App, sampler: &mut Sampler) {
+	sampler.track(app.tracked_pid());
+	app.apply(sampler.sample());
+}
+
+/// The time between two samples, at least the minimum time `sysinfo` needs to compute cpu usage.
+fn interval() -> Duration {
+	config::current()
+		.sampling
+		.interval
+		.max(System::MINIMUM_CPU_UPDATE_INTERVAL)
+}
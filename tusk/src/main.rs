@@ -2,83 +2,64 @@
 //! The Tusk application is a terminal based profiler for
 //! linux based systems.
 
-use std::{error::Error, io, thread, time::Instant};
+use std::{error::Error, io, path::PathBuf, process, time::Instant};
 
+use clap::Parser;
 use crossterm::{
-	event::{self, DisableMouseCapture},
+	event::{self, DisableMouseCapture, EnableMouseCapture},
 	execute,
 	terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 
-use datapoints::{EVENT_TIMEOUT, TICK_TIME};
 use namefn::namefn;
 use ratatui::prelude::*;
-use terminal::{draw::draw, App, Screen};
+use sysinfo::{Pid, PidExt};
+use terminal::{draw::draw, App, Status};
 
 use crate::{
-	datapoints::{NETWORK_CUTOFF, NETWORK_MAX, NETWORK_MIN},
+	cli::Args,
+	config::{error::ConfigError, watch::Watcher},
 	terminal::events::{handle_event, ControlFlow},
 };
 
+/// The `cli` module defines the command line arguments.
+mod cli;
 /// The `config` module takes care of all the programs configuration.
 mod config;
+/// The `control` module changes processes, by signals, priorities and affinity.
+mod control;
+/// The `headless` module runs the non-interactive commands.
+mod headless;
+/// The `sampler` module collects the data in the background.
+mod sampler;
 /// The `terminal` module takes care of all the user interaction happening.
 mod terminal;
 
-/// All data handiling constants are defined here.
-mod datapoints {
-	#![allow(dead_code)]
-
-	use std::time::Duration;
-
-	use memu::units::{KiloByte, MegaByte};
-
-	/// How long a tick should be.
-	pub const TICK_TIME: Duration = Duration::from_millis(50);
-	/// How long `event::pool` should wait for events.
-	pub const EVENT_TIMEOUT: Duration = Duration::from_millis(1);
-	/// How many datapoints for cpu usage should be collected.
-	pub const CPU_USAGE_DATAPOINTS: usize = 100;
-	/// How many datapoints for the network should be collected.
-	pub const NETWORK_DATAPOINTS: usize = 100;
-	/// The amount of kilobites that is displayed at minimum when the lower end cutoff is oversteped.
-	pub const NETWORK_MAX: KiloByte = KiloByte::from_u8(3);
-	pub const NETWORK_CUTOFF: KiloByte = KiloByte::from_u8(8);
-	/// The amount of kilobytes displayed at minimum by the networks graphs.
-	pub const NETWORK_MIN: KiloByte = KiloByte::from_u8(3);
-	/// How many datapoints should be collected on the tracked process.
-	pub const TRACKED_PROCESS_DATAPOINTS: usize = 100;
-	/// The amount of megabytes displayed at minimum by the tracked process.
-	pub const TRACKED_MINIMUM_HIGHEST_MEMORY: MegaByte = MegaByte::from_u8(0);
-	/// How many log messages should be keept track of.
-	pub const LOG_MESSAGES: usize = 100;
-	/// How many ticks should be tracked in debug mode.
-	pub const DEBUG_TICK_DATAPOINTS: usize = 100;
-	/// How many log events should be keep in the log.
-	pub const TRACKED_LOG_EVENTS: usize = 30;
-}
+fn main() -> Result<(), Box<dyn Error>> {
+	let args = Args::parse();
 
-/// Currently hardcoded test tabs.
-pub const TABS: [Screen; 3] = [Screen::Default, Screen::Processes, Screen::Tracked];
+	let config_error = config::init(&args).err();
 
-fn main() -> Result<(), Box<dyn Error>> {
-	assert!(
-		(NETWORK_MIN < NETWORK_CUTOFF) && (NETWORK_CUTOFF < NETWORK_MAX),
-		concat!(
-			"the equality ",
-			stringify!(NETWORK_MIN),
-			" < ",
-			stringify!(NETWORK_CUTOFF),
-			" < ",
-			stringify!(NETWORK_MAX),
-			" does not hold true"
-		)
-	);
+	if let Some(command) = &args.command {
+		if let Some(error) = &config_error {
+			eprintln!("Config error: {}", error);
+		}
+
+		let mut app = new_app(&args);
+		if let Err(error) = headless::run(command, &mut app) {
+			eprintln!("Error: {}", error);
+			process::exit(1);
+		}
+		return Ok(());
+	}
 
 	enable_raw_mode()?;
 	let mut stdout = io::stdout();
 
-	execute!(stdout, EnterAlternateScreen, DisableMouseCapture)?;
+	execute!(stdout, EnterAlternateScreen)?;
+	if config::current().mouse {
+		execute!(stdout, EnableMouseCapture)?;
+	}
 	let backend = CrosstermBackend::new(stdout);
 	let mut terminal = Terminal::new(backend)?;
 
@@ -86,15 +67,11 @@ fn main() -> Result<(), Box<dyn Error>> {
 
 	std::panic::set_hook(Box::new(move |panic| {
 		disable_raw_mode().unwrap();
-		execute!(io::stdout(), LeaveAlternateScreen).unwrap();
+		execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture).unwrap();
 		panic_hook(panic);
 	}));
 
-	let mut app_output = run_app(&mut terminal);
-
-	while let Ok(true) = app_output {
-		app_output = run_app(&mut terminal);
-	}
+	let app_output = run_app(&mut terminal, &args, config_error.as_ref());
 
 	disable_raw_mode()?;
 	execute!(
@@ -110,52 +87,129 @@ fn main() -> Result<(), Box<dyn Error>> {
 	Ok(())
 }
 
-/// If this returns true a reload should be initiated
+/// Creates a new app, tracking the process and showing the tab given on the command line.
+fn new_app(args: &Args) -> App {
+	let mut app = App::new();
+
+	if let Some(pid) = args.pid {
+		app.track(Pid::from_u32(pid));
+	} else if let Some(name) = &args.name {
+		app.track_name(name);
+	}
+
+	if let Some(tab) = args.tab {
+		app.select_tab(tab);
+	}
+
+	app
+}
+
+/// Runs the app until the user quits.
+///
+/// A `config_error` is displayed in the status line of the new app.
 ///
 /// The order of calls should always be this way:
 ///
 /// Prior to looping:
 ///
 /// - new app
+/// - start the sampler
 ///
 /// In each tick:
 ///
-/// - fetch_data
+/// - apply the new samples
 /// - draw
-/// - handle keys
+/// - handle keys, until the next tick or the first key
+/// - reload the configuration if it changed
 ///
 #[namefn]
-fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<bool> {
-	let mut app = App::new();
+fn run_app<B: Backend>(
+	terminal: &mut Terminal<B>,
+	args: &Args,
+	config_error: Option<&ConfigError>,
+) -> io::Result<()> {
+	let mut app = new_app(args);
+	let mut watcher = new_watcher(&mut app);
+	let sampler = sampler::spawn();
+	let mut tracked = None;
+
+	if let Some(error) = config_error {
+		app.set_status(Status::error(format!("Config error: {}", error)));
+	}
 
 	loop {
 		app.log("New tick", NAME);
 
 		let tick_start = Instant::now();
 
-		app.refresh();
+		if app.tracked_pid() != tracked {
+			tracked = app.tracked_pid();
+			sampler.track(tracked);
+		}
+
+		for sample in sampler.samples() {
+			app.apply(sample);
+		}
 
 		let draw_tick = Instant::now();
 		draw(terminal, &mut app)?;
 		app.draw_tick(draw_tick.elapsed());
+		app.working_tick(tick_start.elapsed());
 
-		let event_tick = Instant::now();
-		if event::poll(EVENT_TIMEOUT).unwrap_or(false) {
+		// Waits for input until the next tick, a key ends the tick early so it is drawn immediately.
+		let mut reload = false;
+		let timeout = app.config.tick.saturating_sub(tick_start.elapsed());
+		if event::poll(timeout).unwrap_or(false) {
 			let event = event::read()?;
+			let event_tick = Instant::now();
 			let flow = handle_event(event, &mut app);
+			app.event_tick(event_tick.elapsed());
 			match flow {
 				ControlFlow::Continue => (),
 				ControlFlow::Quit => break,
-				ControlFlow::Reload => return Ok(true),
+				ControlFlow::Reload => reload = true,
 			}
 		}
-		app.event_tick(event_tick.elapsed());
 
-		app.working_tick(tick_start.elapsed());
-		if tick_start.elapsed() <= TICK_TIME {
-			thread::sleep(TICK_TIME - tick_start.elapsed());
+		if reload || watcher.as_mut().is_some_and(Watcher::changed) {
+			reload_config(&mut app, args);
+			watcher = new_watcher(&mut app);
 		}
+
 		app.real_tick(tick_start.elapsed());
 	}
-	Ok(false)
+	Ok(())
+}
+
+/// Loads the configuration again and applies it to `app`, displaying the result in the status line.
+fn reload_config(app: &mut App, args: &Args) {
+	match config::reload(args) {
+		Ok(()) => {
+			app.reload_config();
+			app.set_status(Status::info("Reloaded the configuration"));
+		},
+		Err(error) => app.set_status(Status::error(format!("Config error: {}", error))),
+	}
+}
+
+/// Watches the configuration and theme file, if enabled in the configuration.
+#[namefn]
+fn new_watcher(app: &mut App) -> Option<Watcher> {
+	if !app.config.watch {
+		return None;
+	}
+
+	let files = app.config.files();
+	match Watcher::new(files.iter().map(PathBuf::as_path)) {
+		Ok(watcher) => {
+			for file in &files {
+				app.log(format!("Watching {}", file.display()), NAME);
+			}
+			Some(watcher)
+		},
+		Err(error) => {
+			app.set_status(Status::error(format!("Can't watch the config: {}", error)));
+			None
+		},
+	}
 }
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use sysinfo::Pid;
+
+use super::Sample;
+use crate::config::Config;
+
+/// Describes a value collected by a [`Collector`].
+pub struct Metric {
+	/// The name of the metric, like `cpu.usage`.
+	pub name: &'static str,
+	/// The unit the metric is measured in, like `%` or `KB/s`.
+	pub unit: &'static str,
+	pub description: &'static str,
+}
+
+/// Everything collectors need to know about the sample being taken.
+pub struct Context {
+	/// The configuration active while taking the sample.
+	pub config: Arc<Config>,
+	/// The process the user is tracking, if any.
+	pub tracked: Option<Pid>,
+}
+
+/// A source of metrics.
+///
+/// Every collector fills its own part of a [`Sample`], parts of disabled collectors stay `None`.
+pub trait Collector: Send {
+	/// The name used to enable or disable the collector in the configuration.
+	fn name(&self) -> &'static str;
+
+	/// Describes the metrics this collector produces.
+	fn metrics(&self) -> &'static [Metric];
+
+	/// Prepares the collector, called once before the first sample it takes.
+	fn init(&mut self);
+
+	/// Collects the metrics into `sample`.
+	fn sample(&mut self, context: &Context, sample: &mut Sample);
+}
@@ -0,0 +1,353 @@
+use std::{
+	fs,
+	path::Path,
+	time::{Duration, Instant},
+};
+
+use sysinfo::{Cpu, CpuExt, CpuRefreshKind, RefreshKind, System, SystemExt};
+
+use super::{
+	collector::{Collector, Context, Metric},
+	CpuSample, CpuStat, CpuTimes, Sample, Scaling,
+};
+
+/// The time between two reads of the frequency scaling settings, which rarely change.
+const SCALING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The time between two reads of `/proc/stat`.
+///
+/// The kernel counts cpu time in ticks of usually 10ms, shorter intervals only show noise.
+const STAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Collects the usage and frequency of the cpu.
+pub struct CpuCollector {
+	sys: System,
+	last_scaling: Option<Instant>,
+	/// The counters of the last read of `/proc/stat` and when it happened.
+	last_stat: Option<(Instant, Stat)>,
+}
+
+impl CpuCollector {
+	pub fn new() -> Self {
+		CpuCollector {
+			sys: System::new_with_specifics(RefreshKind::new()),
+			last_scaling: None,
+			last_stat: None,
+		}
+	}
+
+	/// Reads `/proc/stat` if it is due, returning the activity since the last read.
+	fn sample_stat(&mut self) -> Option<CpuStat> {
+		let (last_read, last) = self.last_stat.as_ref()?;
+		if last_read.elapsed() < STAT_INTERVAL {
+			return None;
+		}
+
+		let stat = read_stat()?;
+		let seconds = last_read.elapsed().as_secs_f64();
+		let cpu_stat = CpuStat {
+			total: CpuTimes::between(&last.total, &stat.total),
+			cores: last
+				.cores
+				.iter()
+				.zip(&stat.cores)
+				.map(|(last, current)| CpuTimes::between(last, current))
+				.collect(),
+			context_switches: stat.context_switches.saturating_sub(last.context_switches) as f64
+				/ seconds,
+			interrupts: stat.interrupts.saturating_sub(last.interrupts) as f64 / seconds,
+		};
+
+		self.last_stat = Some((Instant::now(), stat));
+		Some(cpu_stat)
+	}
+}
+
+impl Collector for CpuCollector {
+	fn name(&self) -> &'static str {
+		"cpu"
+	}
+
+	fn metrics(&self) -> &'static [Metric] {
+		&[
+			Metric {
+				name: "cpu.usage",
+				unit: "%",
+				description: "Average usage of all cores",
+			},
+			Metric {
+				name: "cpu.cores",
+				unit: "%",
+				description: "Usage of every core",
+			},
+			Metric {
+				name: "cpu.frequencies",
+				unit: "MHz",
+				description: "Frequency of every core",
+			},
+			Metric {
+				name: "cpu.scaling",
+				unit: "MHz",
+				description: "Governor and frequency limits of every core, if exposed in /sys",
+			},
+			Metric {
+				name: "cpu.frequency",
+				unit: "MHz",
+				description: "Average frequency of all cores",
+			},
+			Metric {
+				name: "cpu.times",
+				unit: "%",
+				description: "Share of user, nice, system, idle, iowait, irq, softirq, steal and guest time, in total and of every core",
+			},
+			Metric {
+				name: "cpu.context_switches",
+				unit: "1/s",
+				description: "Context switches of all cores",
+			},
+			Metric {
+				name: "cpu.interrupts",
+				unit: "1/s",
+				description: "Interrupts serviced by all cores",
+			},
+		]
+	}
+
+	fn init(&mut self) {
+		self.sys
+			.refresh_specifics(RefreshKind::new().with_cpu(CpuRefreshKind::everything()));
+		self.last_stat = read_stat().map(|stat| (Instant::now(), stat));
+	}
+
+	fn sample(&mut self, _context: &Context, sample: &mut Sample) {
+		self.sys.refresh_cpu();
+
+		let scaling_due = self
+			.last_scaling
+			.is_none_or(|last| last.elapsed() >= SCALING_INTERVAL);
+		if scaling_due {
+			self.last_scaling = Some(Instant::now());
+		}
+
+		let stat = self.sample_stat();
+		let cpus = self.sys.cpus();
+		sample.cpu = Some(CpuSample {
+			name: cpus
+				.first()
+				.map_or_else(String::new, |cpu| cpu.brand().to_owned()),
+			frequency: compute_frequency(cpus),
+			usage: compute_usage(cpus),
+			cores: cpus.iter().map(|core| core.cpu_usage()).collect(),
+			frequencies: cpus.iter().map(|core| core.frequency()).collect(),
+			scaling: scaling_due.then(|| cpus.iter().map(read_scaling).collect()),
+			stat,
+		});
+	}
+}
+
+/// The average frequency of all cores, 0 if none are known.
+fn compute_frequency(vec: &[Cpu]) -> u64 {
+	vec.iter()
+		.map(|core| core.frequency())
+		.sum::<u64>()
+		.checked_div(vec.len() as u64)
+		.unwrap_or(0)
+}
+
+/// The average usage of all cores, 0 if none are known.
+fn compute_usage(vec: &[Cpu]) -> f32 {
+	if vec.is_empty() {
+		return 0.0;
+	}
+	vec.iter().map(|core| core.cpu_usage()).sum::<f32>() / vec.len() as f32
+}
+
+/// Reads the frequency scaling settings of `core` from `/sys`.
+fn read_scaling(core: &Cpu) -> Scaling {
+	let dir = Path::new("/sys/devices/system/cpu")
+		.join(core.name())
+		.join("cpufreq");
+	let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+	// The kernel reports frequencies in kHz.
+	let read_mhz = |file: &str| {
+		read(file)
+			.and_then(|khz| khz.trim().parse::<u64>().ok())
+			.map(|khz| khz / 1000)
+	};
+
+	Scaling {
+		governor: read("scaling_governor").map(|governor| governor.trim().to_owned()),
+		min: read_mhz("scaling_min_freq"),
+		max: read_mhz("scaling_max_freq"),
+	}
+}
+
+/// The cumulative counters of `/proc/stat`.
+#[derive(PartialEq, Debug)]
+struct Stat {
+	/// The time spent in every state by all cores, in ticks.
+	total: [u64; 10],
+	/// The time spent in every state by every core, in ticks.
+	cores: Vec<[u64; 10]>,
+	context_switches: u64,
+	interrupts: u64,
+}
+
+/// Reads `/proc/stat`, `None` if it can't be read.
+fn read_stat() -> Option<Stat> {
+	fs::read_to_string("/proc/stat")
+		.ok()
+		.map(|content| parse_stat(&content))
+}
+
+/// Parses the contents of `/proc/stat`, counters missing from older kernels are 0.
+fn parse_stat(content: &str) -> Stat {
+	let mut stat = Stat {
+		total: [0; 10],
+		cores: Vec::new(),
+		context_switches: 0,
+		interrupts: 0,
+	};
+
+	for line in content.lines() {
+		let mut fields = line.split_whitespace();
+		let Some(key) = fields.next() else {
+			continue;
+		};
+		let mut values = fields.map(|field| field.parse::<u64>().unwrap_or_default());
+
+		match key {
+			"cpu" => values
+				.zip(&mut stat.total)
+				.for_each(|(value, time)| *time = value),
+			cpu if cpu.starts_with("cpu") => {
+				let mut times = [0; 10];
+				values
+					.zip(&mut times)
+					.for_each(|(value, time)| *time = value);
+				stat.cores.push(times);
+			},
+			// The first value is the total, the others count every interrupt separately.
+			"intr" => stat.interrupts = values.next().unwrap_or_default(),
+			"ctxt" => stat.context_switches = values.next().unwrap_or_default(),
+			_ => (),
+		}
+	}
+
+	stat
+}
+
+impl CpuTimes {
+	/// Computes the share of every state between two reads of the counters of a cpu.
+	///
+	/// The kernel counts guest time as user or nice time as well, here it is only counted as guest.
+	fn between(last: &[u64; 10], current: &[u64; 10]) -> Self {
+		let [user, nice, system, idle, iowait, irq, softirq, steal, guest, guest_nice] =
+			std::array::from_fn(|i| current[i].saturating_sub(last[i]) as f32);
+		let total = user + nice + system + idle + iowait + irq + softirq + steal;
+		if total == 0.0 {
+			return CpuTimes::default();
+		}
+		let share = |time: f32| time / total * 100.0;
+
+		CpuTimes {
+			user: share((user - guest).max(0.0)),
+			nice: share((nice - guest_nice).max(0.0)),
+			system: share(system),
+			idle: share(idle),
+			iowait: share(iowait),
+			irq: share(irq),
+			softirq: share(softirq),
+			steal: share(steal),
+			guest: share(guest + guest_nice),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const STAT: &str = "\
+cpu  1000 200 300 8000 100 50 50 0 400 100
+cpu0 500 100 150 4000 50 25 25 0 200 50
+cpu1 500 100 150 4000 50 25 25 0 200 50
+intr 12345 0 9 0 0
+ctxt 67890
+btime 1700000000
+processes 4242
+procs_running 2
+procs_blocked 0
+softirq 999 0 1 2 3
+";
+
+	#[test]
+	fn parse() {
+		let stat = parse_stat(STAT);
+
+		assert_eq!(stat.total, [1000, 200, 300, 8000, 100, 50, 50, 0, 400, 100]);
+		assert_eq!(stat.cores.len(), 2);
+		assert_eq!(stat.cores[1], [500, 100, 150, 4000, 50, 25, 25, 0, 200, 50]);
+		assert_eq!(stat.interrupts, 12345);
+		assert_eq!(stat.context_switches, 67890);
+	}
+
+	#[test]
+	fn missing_columns() {
+		// Kernels before 2.6.33 have no guest_nice, before 2.6.24 no guest or steal either.
+		let stat = parse_stat("cpu 10 20 30 40 50 60 70\ncpu0 1 2 3 4\nintr\n");
+
+		assert_eq!(stat.total, [10, 20, 30, 40, 50, 60, 70, 0, 0, 0]);
+		assert_eq!(stat.cores, [[1, 2, 3, 4, 0, 0, 0, 0, 0, 0]]);
+		assert_eq!(stat.interrupts, 0);
+		assert_eq!(stat.context_switches, 0);
+
+		assert_eq!(
+			parse_stat(""),
+			Stat {
+				total: [0; 10],
+				cores: Vec::new(),
+				context_switches: 0,
+				interrupts: 0,
+			}
+		);
+	}
+
+	#[test]
+	fn guest_time_is_not_counted_twice() {
+		let last = [0; 10];
+		// 100 ticks, of which 20 user and 10 nice ticks ran guests.
+		let current = [30, 20, 10, 25, 5, 2, 3, 5, 20, 10];
+
+		let times = CpuTimes::between(&last, &current);
+		let shares = [
+			times.user,
+			times.nice,
+			times.system,
+			times.idle,
+			times.iowait,
+			times.irq,
+			times.softirq,
+			times.steal,
+			times.guest,
+		];
+		let expected = [10.0, 10.0, 10.0, 25.0, 5.0, 2.0, 3.0, 5.0, 30.0];
+		for (share, expected) in shares.into_iter().zip(expected) {
+			assert!((share - expected).abs() < 1e-4, "{:?}", times);
+		}
+	}
+
+	#[test]
+	fn counters_going_backwards() {
+		// Counters that wrapped or were reset count as no time instead of a huge one.
+		let last = [500, 0, 0, 500, 0, 0, 0, 0, 0, 0];
+		let current = [100, 0, 0, 600, 0, 0, 0, 0, 0, 0];
+		let times = CpuTimes::between(&last, &current);
+		assert_eq!(times.user, 0.0);
+		assert_eq!(times.idle, 100.0);
+
+		assert_eq!(CpuTimes::between(&current, &last).user, 100.0);
+		assert_eq!(CpuTimes::between(&current, &current), CpuTimes::default());
+		assert_eq!(CpuTimes::between(&last, &[0; 10]), CpuTimes::default());
+	}
+}
@@ -0,0 +1,211 @@
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	time::{Duration, Instant},
+};
+
+use memu::units::Byte;
+use sysinfo::{DiskExt, RefreshKind, System, SystemExt};
+
+use super::{
+	collector::{Collector, Context, Metric},
+	DeviceSample, DiskSample, FilesystemSample, Sample,
+};
+
+/// The time between two refreshes of the mounted filesystems and block devices, which change
+/// slowly.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The size of a sector in `/proc/diskstats`, independent of the device.
+const SECTOR_SIZE: u64 = 512;
+
+/// Collects the activity of block devices from `/proc/diskstats` and the usage of filesystems.
+pub struct DiskCollector {
+	sys: System,
+	/// The names of the whole block devices, from `/sys/block`.
+	devices: HashSet<String>,
+	last_stats: HashMap<String, DiskStats>,
+	last_sample: Instant,
+	last_refresh: Option<Instant>,
+}
+
+impl DiskCollector {
+	pub fn new() -> Self {
+		DiskCollector {
+			sys: System::new_with_specifics(RefreshKind::new()),
+			devices: HashSet::new(),
+			last_stats: HashMap::new(),
+			last_sample: Instant::now(),
+			last_refresh: None,
+		}
+	}
+}
+
+impl Collector for DiskCollector {
+	fn name(&self) -> &'static str {
+		"disk"
+	}
+
+	fn metrics(&self) -> &'static [Metric] {
+		&[
+			Metric {
+				name: "disk.read",
+				unit: "B/s",
+				description: "Data read from every block device",
+			},
+			Metric {
+				name: "disk.written",
+				unit: "B/s",
+				description: "Data written to every block device",
+			},
+			Metric {
+				name: "disk.iops",
+				unit: "1/s",
+				description: "Completed reads and writes of every block device",
+			},
+			Metric {
+				name: "disk.queue",
+				unit: "",
+				description: "Average number of requests waiting on every block device",
+			},
+			Metric {
+				name: "disk.utilization",
+				unit: "%",
+				description: "Time every block device was busy",
+			},
+			Metric {
+				name: "filesystems",
+				unit: "B",
+				description: "Size and usage of every mounted filesystem",
+			},
+		]
+	}
+
+	fn init(&mut self) {
+		self.devices = read_devices();
+		self.last_stats = read_diskstats(&self.devices);
+		self.last_sample = Instant::now();
+	}
+
+	fn sample(&mut self, _context: &Context, sample: &mut Sample) {
+		let refresh_due = self
+			.last_refresh
+			.is_none_or(|last| last.elapsed() >= REFRESH_INTERVAL);
+		if refresh_due {
+			self.last_refresh = Some(Instant::now());
+			self.devices = read_devices();
+		}
+
+		let elapsed = self.last_sample.elapsed();
+		let stats = read_diskstats(&self.devices);
+		self.last_sample = Instant::now();
+
+		let mut devices: Vec<DeviceSample> = stats
+			.iter()
+			.filter_map(|(name, stats)| {
+				let last = self.last_stats.get(name)?;
+				Some(stats.since(last, name, elapsed))
+			})
+			.collect();
+		devices.sort_by(|a, b| a.name.cmp(&b.name));
+		self.last_stats = stats;
+
+		let filesystems = refresh_due.then(|| {
+			self.sys.refresh_disks_list();
+			self.sys
+				.disks()
+				.iter()
+				.map(|disk| FilesystemSample {
+					mount_point: disk.mount_point().to_owned(),
+					device: disk.name().to_string_lossy().into_owned(),
+					file_system: String::from_utf8_lossy(disk.file_system()).into_owned(),
+					total: Byte::new(disk.total_space()),
+					used: Byte::new(disk.total_space().saturating_sub(disk.available_space())),
+				})
+				.collect()
+		});
+
+		sample.disk = Some(DiskSample {
+			devices,
+			filesystems,
+		});
+	}
+}
+
+/// The counters of a block device, see the kernel's `Documentation/admin-guide/iostats.rst`.
+#[derive(Clone, Copy)]
+struct DiskStats {
+	reads: u64,
+	sectors_read: u64,
+	writes: u64,
+	sectors_written: u64,
+	/// Milliseconds spent doing I/O.
+	busy: u64,
+	/// Milliseconds spent doing I/O, weighted by the number of requests in flight.
+	weighted_busy: u64,
+}
+
+impl DiskStats {
+	/// Computes the rates between `last` and these counters.
+	fn since(&self, last: &DiskStats, name: &str, elapsed: Duration) -> DeviceSample {
+		let seconds = elapsed.as_secs_f64();
+		let milliseconds = seconds * 1000.0;
+		let rate = |now: u64, last: u64| now.saturating_sub(last) as f64 / seconds;
+
+		DeviceSample {
+			name: name.to_owned(),
+			read: Byte::from_f64(rate(self.sectors_read, last.sectors_read) * SECTOR_SIZE as f64),
+			written: Byte::from_f64(
+				rate(self.sectors_written, last.sectors_written) * SECTOR_SIZE as f64,
+			),
+			read_iops: rate(self.reads, last.reads),
+			write_iops: rate(self.writes, last.writes),
+			queue: self.weighted_busy.saturating_sub(last.weighted_busy) as f64 / milliseconds,
+			utilization: (self.busy.saturating_sub(last.busy) as f64 / milliseconds * 100.0)
+				.min(100.0),
+		}
+	}
+}
+
+/// Lists the names of the whole block devices, partitions aren't in `/sys/block`.
+fn read_devices() -> HashSet<String> {
+	let Ok(entries) = fs::read_dir("/sys/block") else {
+		return HashSet::new();
+	};
+
+	entries
+		.filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+		.collect()
+}
+
+/// Reads the counters of the block devices in `devices`, leaving out unused loop and ram devices.
+fn read_diskstats(devices: &HashSet<String>) -> HashMap<String, DiskStats> {
+	let Ok(diskstats) = fs::read_to_string("/proc/diskstats") else {
+		return HashMap::new();
+	};
+
+	diskstats
+		.lines()
+		.filter_map(|line| {
+			let fields: Vec<&str> = line.split_whitespace().collect();
+			let name = *fields.get(2)?;
+			let field = |index: usize| fields.get(index).and_then(|value| value.parse().ok());
+
+			let stats = DiskStats {
+				reads: field(3)?,
+				sectors_read: field(5)?,
+				writes: field(7)?,
+				sectors_written: field(9)?,
+				busy: field(12)?,
+				weighted_busy: field(13)?,
+			};
+
+			let is_device = devices.contains(name);
+			let is_unused = (name.starts_with("loop") || name.starts_with("ram"))
+				&& stats.reads == 0
+				&& stats.writes == 0;
+
+			(is_device && !is_unused).then(|| (name.to_owned(), stats))
+		})
+		.collect()
+}
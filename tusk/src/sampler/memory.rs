@@ -0,0 +1,121 @@
+use std::fs;
+
+use memu::units::Byte;
+
+use super::{
+	collector::{Collector, Context, Metric},
+	MemorySample, Sample,
+};
+
+/// Collects the memory and swap of the system from `/proc/meminfo`.
+///
+/// `sysinfo` isn't used since it reports the cgroup limit as total memory, which is practically
+/// unlimited inside most containers, and doesn't expose buffers and the page cache.
+pub struct MemoryCollector;
+
+impl MemoryCollector {
+	pub fn new() -> Self {
+		MemoryCollector
+	}
+}
+
+impl Collector for MemoryCollector {
+	fn name(&self) -> &'static str {
+		"memory"
+	}
+
+	fn metrics(&self) -> &'static [Metric] {
+		&[
+			Metric {
+				name: "memory.used",
+				unit: "B",
+				description: "Memory used by processes",
+			},
+			Metric {
+				name: "memory.buffers",
+				unit: "B",
+				description: "Memory used by kernel buffers",
+			},
+			Metric {
+				name: "memory.cached",
+				unit: "B",
+				description: "Memory used by the page cache",
+			},
+			Metric {
+				name: "memory.available",
+				unit: "B",
+				description: "Memory available for new processes",
+			},
+			Metric {
+				name: "swap.used",
+				unit: "B",
+				description: "Swap in use",
+			},
+		]
+	}
+
+	fn init(&mut self) {}
+
+	fn sample(&mut self, _context: &Context, sample: &mut Sample) {
+		sample.memory = read_meminfo();
+	}
+}
+
+/// Reads the memory of the system, `None` if `/proc/meminfo` can't be read.
+fn read_meminfo() -> Option<MemorySample> {
+	let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+
+	let mut values = MemInfo::default();
+	for line in meminfo.lines() {
+		let mut parts = line.split_whitespace();
+		let (Some(key), Some(kilobytes)) = (parts.next(), parts.next()) else {
+			continue;
+		};
+		// The kernel reports kibibytes, even though the unit reads `kB`.
+		let bytes = kilobytes.parse::<u64>().unwrap_or_default() * 1024;
+
+		let field = match key {
+			"MemTotal:" => &mut values.total,
+			"MemFree:" => &mut values.free,
+			"MemAvailable:" => &mut values.available,
+			"Buffers:" => &mut values.buffers,
+			"Cached:" => &mut values.cached,
+			"SReclaimable:" => &mut values.reclaimable,
+			"SwapTotal:" => &mut values.swap_total,
+			"SwapFree:" => &mut values.swap_free,
+			_ => continue,
+		};
+		*field = bytes;
+	}
+
+	// Reclaimable kernel memory is freed like the page cache, so it is counted towards it.
+	let cached = values.cached + values.reclaimable;
+	let used = values
+		.total
+		.saturating_sub(values.free)
+		.saturating_sub(values.buffers)
+		.saturating_sub(cached);
+
+	Some(MemorySample {
+		total: Byte::new(values.total),
+		used: Byte::new(used),
+		buffers: Byte::new(values.buffers),
+		cached: Byte::new(cached),
+		available: Byte::new(values.available),
+		swap_total: Byte::new(values.swap_total),
+		swap_used: Byte::new(values.swap_total.saturating_sub(values.swap_free)),
+	})
+}
+
+/// The values of `/proc/meminfo` in bytes.
+#[derive(Default)]
+struct MemInfo {
+	total: u64,
+	free: u64,
+	available: u64,
+	buffers: u64,
+	cached: u64,
+	reclaimable: u64,
+	swap_total: u64,
+	swap_free: u64,
+}
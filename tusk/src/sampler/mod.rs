@@ -0,0 +1,439 @@
+/// The [`Collector`] trait implemented by all metric sources.
+pub mod collector;
+/// Collects the usage and frequency of the cpu.
+mod cpu;
+/// Collects the activity of block devices and the usage of filesystems.
+mod disk;
+/// Collects the memory and swap.
+mod memory;
+/// Collects the network traffic.
+mod network;
+/// Collects the pressure stall information.
+mod pressure;
+/// Collects the processes and the tracked process.
+mod processes;
+/// Collects the temperature sensors.
+mod sensors;
+/// Collects the host, uptime, load and users of the system.
+mod system;
+
+use std::{
+	path::PathBuf,
+	sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryIter},
+	thread,
+	time::{Duration, Instant},
+};
+
+use memu::units::{Byte, KiloByte, MegaByte};
+use sysinfo::{LoadAvg, Pid};
+
+use self::collector::{Collector, Context};
+use crate::{config, terminal::Process};
+
+/// The data collected at one point in time.
+///
+/// Every part is `None` if its collector is disabled or didn't collect it this time.
+#[derive(Default)]
+pub struct Sample {
+	pub cpu: Option<CpuSample>,
+	pub memory: Option<MemorySample>,
+	pub disk: Option<DiskSample>,
+	pub network: Option<NetworkSample>,
+	pub system: Option<SystemSample>,
+	/// Every temperature sensor, sorted by label.
+	pub sensors: Option<Vec<SensorSample>>,
+	pub pressure: Option<PressureSample>,
+	/// All processes sorted by name, only present if they were sampled this time.
+	pub processes: Option<Vec<Process>>,
+	pub tracked: Option<TrackedSample>,
+	/// How long taking this sample took.
+	pub duration: Duration,
+}
+
+/// The cpu in a sample.
+pub struct CpuSample {
+	pub name: String,
+	/// Average frequency of all cores in MHz.
+	pub frequency: u64,
+	/// Average usage of all cores in percent.
+	pub usage: f32,
+	/// Usage of every core in percent, in the order of their numbers.
+	pub cores: Vec<f32>,
+	/// Frequency of every core in MHz.
+	pub frequencies: Vec<u64>,
+	/// The frequency scaling settings of every core, only present if they were read this time.
+	pub scaling: Option<Vec<Scaling>>,
+	/// The activity from `/proc/stat`, only present if it was read this time.
+	pub stat: Option<CpuStat>,
+}
+
+/// The activity of the cpu since the last read of `/proc/stat`.
+#[derive(Clone, Debug)]
+pub struct CpuStat {
+	/// The time spent in every state by all cores together.
+	pub total: CpuTimes,
+	/// The time spent in every state by every core.
+	pub cores: Vec<CpuTimes>,
+	/// Context switches per second.
+	pub context_switches: f64,
+	/// Interrupts per second.
+	pub interrupts: f64,
+}
+
+/// The share of time a cpu spent in every state in percent, adding up to 100.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct CpuTimes {
+	/// Running processes.
+	pub user: f32,
+	/// Running processes with a positive niceness.
+	pub nice: f32,
+	/// Running the kernel.
+	pub system: f32,
+	pub idle: f32,
+	/// Idle while waiting for disk or network io.
+	pub iowait: f32,
+	/// Servicing hardware interrupts.
+	pub irq: f32,
+	/// Servicing software interrupts.
+	pub softirq: f32,
+	/// Waiting for the hypervisor to run a virtual cpu.
+	pub steal: f32,
+	/// Running virtual machines.
+	pub guest: f32,
+}
+
+/// The frequency scaling settings of a core, read from `/sys/devices/system/cpu/cpu*/cpufreq`.
+///
+/// Every value is `None` if the kernel doesn't expose it, for example inside virtual machines.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct Scaling {
+	/// The governor deciding the frequency, like `powersave` or `performance`.
+	pub governor: Option<String>,
+	/// The lowest frequency the governor may choose in MHz.
+	pub min: Option<u64>,
+	/// The highest frequency the governor may choose in MHz.
+	pub max: Option<u64>,
+}
+
+/// The memory and swap in a sample.
+///
+/// `used`, `buffers`, `cached` and the free memory add up to `total`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MemorySample {
+	pub total: Byte,
+	/// Memory used by processes.
+	pub used: Byte,
+	pub buffers: Byte,
+	pub cached: Byte,
+	/// Memory available for new processes without swapping, including most of the cache.
+	pub available: Byte,
+	pub swap_total: Byte,
+	pub swap_used: Byte,
+}
+
+/// The disks in a sample.
+pub struct DiskSample {
+	/// Every block device sorted by name, rates are per second since the last sample.
+	pub devices: Vec<DeviceSample>,
+	/// Every mounted filesystem, only present if they were read this time.
+	pub filesystems: Option<Vec<FilesystemSample>>,
+}
+
+/// The activity of a block device.
+#[derive(Clone, Debug)]
+pub struct DeviceSample {
+	/// The name of the device, like `sda` or `nvme0n1`.
+	pub name: String,
+	/// Bytes read per second.
+	pub read: Byte,
+	/// Bytes written per second.
+	pub written: Byte,
+	/// Reads completed per second.
+	pub read_iops: f64,
+	/// Writes completed per second.
+	pub write_iops: f64,
+	/// The average number of requests in flight.
+	pub queue: f64,
+	/// The share of time the device was busy in percent.
+	pub utilization: f64,
+}
+
+/// The usage of a mounted filesystem.
+#[derive(Clone, Debug)]
+pub struct FilesystemSample {
+	pub mount_point: PathBuf,
+	/// The device the filesystem is on.
+	pub device: String,
+	/// The type of the filesystem, like `ext4`.
+	pub file_system: String,
+	pub total: Byte,
+	pub used: Byte,
+}
+
+/// The network traffic in a sample, per second since the last sample.
+pub struct NetworkSample {
+	/// Bytes received by all included interfaces.
+	pub network_in: Byte,
+	/// Bytes transmitted by all included interfaces.
+	pub network_out: Byte,
+	/// Every included interface, sorted by name.
+	pub interfaces: Vec<InterfaceSample>,
+}
+
+/// The traffic of a network interface.
+#[derive(Clone, Debug)]
+pub struct InterfaceSample {
+	pub name: String,
+	/// Bytes received per second.
+	pub received: Byte,
+	/// Bytes transmitted per second.
+	pub transmitted: Byte,
+	/// Packets received per second.
+	pub packets_received: f64,
+	/// Packets transmitted per second.
+	pub packets_transmitted: f64,
+	/// Receive errors since the interface came up.
+	pub errors_received: u64,
+	/// Transmit errors since the interface came up.
+	pub errors_transmitted: u64,
+	/// Received packets dropped since the interface came up.
+	pub dropped_received: u64,
+	/// Packets dropped before transmitting since the interface came up.
+	pub dropped_transmitted: u64,
+}
+
+/// The system in a sample.
+#[derive(Clone, Debug)]
+pub struct SystemSample {
+	pub host_name: Option<String>,
+	pub kernel: Option<String>,
+	/// The name and version of the operating system, like `Ubuntu 22.04`.
+	pub os: Option<String>,
+	/// Seconds since the unix epoch.
+	pub boot_time: u64,
+	pub uptime: Duration,
+	pub load: LoadAvg,
+	/// The number of distinct users logged in.
+	pub users: usize,
+	pub processes: usize,
+	/// The number of threads of all processes.
+	pub threads: usize,
+}
+
+/// The temperatures of a sensor in degrees celsius.
+///
+/// Temperatures that can't be read are `NaN`.
+#[derive(Clone, Debug)]
+pub struct SensorSample {
+	/// The name of the chip and sensor, like `coretemp Package id 0`.
+	pub label: String,
+	pub temperature: f32,
+	/// The highest temperature the kernel reports, or the current one if it doesn't.
+	pub max: f32,
+	/// The temperature at which the hardware is in danger, if the kernel reports it.
+	pub critical: Option<f32>,
+}
+
+/// The pressure stall information in a sample.
+#[derive(Clone, Debug)]
+pub struct PressureSample {
+	/// The pressure of the whole system, from `/proc/pressure`.
+	pub system: ResourcePressure,
+	/// The pressure of the cgroup of the tracked process, `None` if no process is tracked or its
+	/// cgroup doesn't expose pressure.
+	pub tracked: Option<CgroupPressure>,
+}
+
+/// The pressure of a cgroup.
+#[derive(Clone, Debug)]
+pub struct CgroupPressure {
+	/// The path of the cgroup, like `/user.slice`.
+	pub path: String,
+	pub pressure: ResourcePressure,
+}
+
+/// The pressure on every resource, each `None` if the kernel doesn't expose it.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ResourcePressure {
+	pub cpu: Option<Pressure>,
+	pub memory: Option<Pressure>,
+	pub io: Option<Pressure>,
+}
+
+/// The pressure on a resource.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Pressure {
+	/// The time at least one task was stalled.
+	pub some: Stall,
+	/// The time all tasks were stalled at once, `None` for the cpu on older kernels.
+	pub full: Option<Stall>,
+}
+
+/// The share of time tasks were stalled waiting for a resource in percent, averaged over 10, 60
+/// and 300 seconds.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Stall {
+	pub avg10: f32,
+	pub avg60: f32,
+	pub avg300: f32,
+}
+
+/// The tracked process in a sample.
+pub struct TrackedSample {
+	pub pid: Pid,
+	/// The process, `None` if it doesn't exist anymore.
+	pub process: Option<TrackedData>,
+}
+
+/// The data of the tracked process.
+pub struct TrackedData {
+	pub process: Process,
+	/// Bytes written per second since the last sample.
+	pub written: KiloByte,
+	/// Bytes read per second since the last sample.
+	pub read: KiloByte,
+}
+
+/// Returns all collectors, enabled or not.
+pub fn collectors() -> Vec<Box<dyn Collector>> {
+	vec![
+		Box::new(cpu::CpuCollector::new()),
+		Box::new(memory::MemoryCollector::new()),
+		Box::new(disk::DiskCollector::new()),
+		Box::new(network::NetworkCollector::new()),
+		Box::new(processes::ProcessCollector::new()),
+		Box::new(pressure::PressureCollector::new()),
+		Box::new(system::SystemCollector::new()),
+		Box::new(sensors::SensorCollector::new()),
+	]
+}
+
+/// A collector and whether it was initialized.
+struct Entry {
+	collector: Box<dyn Collector>,
+	initialized: bool,
+}
+
+/// Takes samples of the system using all collectors enabled in the configuration.
+///
+/// Collectors are initialized the first time they are enabled, so disabled ones cost nothing.
+pub struct Sampler {
+	collectors: Vec<Entry>,
+	tracked: Option<Pid>,
+}
+
+impl Sampler {
+	/// Creates a new sampler, initializing all enabled collectors.
+	pub fn new() -> Self {
+		let mut sampler = Sampler {
+			collectors: collectors()
+				.into_iter()
+				.map(|collector| Entry {
+					collector,
+					initialized: false,
+				})
+				.collect(),
+			tracked: None,
+		};
+		sampler.init(&config::current());
+		sampler
+	}
+
+	/// Initializes the enabled collectors that weren't yet.
+	fn init(&mut self, config: &config::Config) {
+		for entry in &mut self.collectors {
+			if !entry.initialized && config.collectors.is_enabled(entry.collector.name()) {
+				entry.collector.init();
+				entry.initialized = true;
+			}
+		}
+	}
+
+	/// Sets the process sampled every time.
+	pub fn track(&mut self, pid: Option<Pid>) {
+		self.tracked = pid;
+	}
+
+	/// Takes a sample.
+	pub fn sample(&mut self) -> Sample {
+		let start = Instant::now();
+		let context = Context {
+			config: config::current(),
+			tracked: self.tracked,
+		};
+		// Collectors may have been enabled by reloading the configuration.
+		self.init(&context.config);
+
+		let mut sample = Sample::default();
+		for entry in &mut self.collectors {
+			if context.config.collectors.is_enabled(entry.collector.name()) {
+				entry.collector.sample(&context, &mut sample);
+			}
+		}
+
+		sample.duration = start.elapsed();
+		sample
+	}
+}
+
+/// Messages sent to the sampler thread.
+enum Command {
+	Track(Option<Pid>),
+}
+
+/// A sampler running in its own thread.
+///
+/// The thread stops once the handle is dropped.
+pub struct SamplerHandle {
+	samples: Receiver<Sample>,
+	commands: Sender<Command>,
+}
+
+impl SamplerHandle {
+	/// Returns the samples taken since the last call, without blocking.
+	pub fn samples(&self) -> TryIter<'_, Sample> {
+		self.samples.try_iter()
+	}
+
+	/// Sets the process sampled every time.
+	pub fn track(&self, pid: Option<Pid>) {
+		// Only fails if the thread stopped, in which case there is nothing to track anymore.
+		let _ = self.commands.send(Command::Track(pid));
+	}
+}
+
+/// Samples waiting to be taken by the app, the sampler waits once this many are queued.
+const QUEUED_SAMPLES: usize = 16;
+
+/// Starts a sampler in its own thread, taking a sample every `sampling.interval`.
+pub fn spawn() -> SamplerHandle {
+	let (sample_sender, samples) = mpsc::sync_channel(QUEUED_SAMPLES);
+	let (commands, command_receiver) = mpsc::channel();
+
+	thread::spawn(move || {
+		let mut sampler = Sampler::new();
+		loop {
+			let start = Instant::now();
+
+			if sample_sender.send(sampler.sample()).is_err() {
+				return;
+			}
+
+			// Waiting on the commands instead of sleeping lets the sampler react to them immediately.
+			let interval = config::current().sampling.interval;
+			while let Some(remaining) = interval.checked_sub(start.elapsed()) {
+				match command_receiver.recv_timeout(remaining) {
+					Ok(Command::Track(pid)) => sampler.track(pid),
+					Err(RecvTimeoutError::Timeout) => break,
+					Err(RecvTimeoutError::Disconnected) => return,
+				}
+			}
+		}
+	});
+
+	SamplerHandle { samples, commands }
+}
+
+fn per_second(data: MegaByte, elapsed: Duration) -> KiloByte {
+	KiloByte::from(data.as_f64() / elapsed.as_secs_f64())
+}
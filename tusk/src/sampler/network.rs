@@ -0,0 +1,130 @@
+use std::{
+	fs,
+	path::Path,
+	time::{Duration, Instant},
+};
+
+use memu::units::Byte;
+use sysinfo::{NetworkData, NetworkExt, NetworksExt, RefreshKind, System, SystemExt};
+
+use super::{
+	collector::{Collector, Context, Metric},
+	InterfaceSample, NetworkSample, Sample,
+};
+
+/// The time between two refreshes of the interface list, interfaces like `docker0` come and go.
+const LIST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Collects the traffic of the network interfaces included in the configuration.
+pub struct NetworkCollector {
+	sys: System,
+	last_sample: Instant,
+	last_list: Instant,
+}
+
+impl NetworkCollector {
+	pub fn new() -> Self {
+		NetworkCollector {
+			sys: System::new_with_specifics(RefreshKind::new()),
+			last_sample: Instant::now(),
+			last_list: Instant::now(),
+		}
+	}
+}
+
+impl Collector for NetworkCollector {
+	fn name(&self) -> &'static str {
+		"network"
+	}
+
+	fn metrics(&self) -> &'static [Metric] {
+		&[
+			Metric {
+				name: "network.in",
+				unit: "B/s",
+				description: "Data received by all included interfaces",
+			},
+			Metric {
+				name: "network.out",
+				unit: "B/s",
+				description: "Data transmitted by all included interfaces",
+			},
+			Metric {
+				name: "network.interfaces",
+				unit: "B/s",
+				description:
+					"Data and packets per second, errors and drops of every included interface",
+			},
+		]
+	}
+
+	fn init(&mut self) {
+		self.sys
+			.refresh_specifics(RefreshKind::new().with_networks().with_networks_list());
+		self.last_sample = Instant::now();
+		self.last_list = Instant::now();
+	}
+
+	fn sample(&mut self, context: &Context, sample: &mut Sample) {
+		let elapsed = self.last_sample.elapsed();
+		if self.last_list.elapsed() >= LIST_INTERVAL {
+			// Also refreshes the data of every interface.
+			self.sys.refresh_networks_list();
+			self.last_list = Instant::now();
+		} else {
+			self.sys.refresh_networks();
+		}
+		self.last_sample = Instant::now();
+
+		let mut interfaces: Vec<(&String, &NetworkData)> = self
+			.sys
+			.networks()
+			.iter()
+			.filter(|(name, _)| context.config.interfaces.is_included(name))
+			.collect();
+		interfaces.sort_by_key(|(name, _)| *name);
+
+		let received = interfaces
+			.iter()
+			.map(|(_, data)| data.received())
+			.sum::<u64>();
+		let transmitted = interfaces
+			.iter()
+			.map(|(_, data)| data.transmitted())
+			.sum::<u64>();
+
+		sample.network = Some(NetworkSample {
+			network_in: Byte::from_f64(received as f64 / elapsed.as_secs_f64()),
+			network_out: Byte::from_f64(transmitted as f64 / elapsed.as_secs_f64()),
+			interfaces: interfaces
+				.into_iter()
+				.map(|(name, data)| interface(name, data, elapsed))
+				.collect(),
+		});
+	}
+}
+
+/// Computes the rates of the interface called `name` since the last sample.
+fn interface(name: &str, data: &NetworkData, elapsed: Duration) -> InterfaceSample {
+	let seconds = elapsed.as_secs_f64();
+	let statistics = Path::new("/sys/class/net").join(name).join("statistics");
+	// `sysinfo` doesn't expose dropped packets.
+	let read_dropped = |file: &str| {
+		fs::read_to_string(statistics.join(file))
+			.ok()
+			.and_then(|dropped| dropped.trim().parse().ok())
+			.unwrap_or_default()
+	};
+
+	InterfaceSample {
+		name: name.to_owned(),
+		received: Byte::from_f64(data.received() as f64 / seconds),
+		transmitted: Byte::from_f64(data.transmitted() as f64 / seconds),
+		packets_received: data.packets_received() as f64 / seconds,
+		packets_transmitted: data.packets_transmitted() as f64 / seconds,
+		errors_received: data.total_errors_on_received(),
+		errors_transmitted: data.total_errors_on_transmitted(),
+		dropped_received: read_dropped("rx_dropped"),
+		dropped_transmitted: read_dropped("tx_dropped"),
+	}
+}
@@ -0,0 +1,144 @@
+use std::{
+	fs,
+	path::Path,
+	time::{Duration, Instant},
+};
+
+use super::{
+	collector::{Collector, Context, Metric},
+	CgroupPressure, Pressure, PressureSample, ResourcePressure, Sample, Stall,
+};
+
+/// The time between two samples, the kernel only updates the averages every two seconds.
+const PRESSURE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Where the cgroup v2 hierarchy is mounted, either on its own or next to the v1 hierarchies.
+const CGROUP_ROOTS: [&str; 2] = ["/sys/fs/cgroup", "/sys/fs/cgroup/unified"];
+
+/// Collects the pressure stall information of the system and the cgroup of the tracked process.
+pub struct PressureCollector {
+	last_sample: Option<Instant>,
+}
+
+impl PressureCollector {
+	pub fn new() -> Self {
+		PressureCollector { last_sample: None }
+	}
+}
+
+impl Collector for PressureCollector {
+	fn name(&self) -> &'static str {
+		"pressure"
+	}
+
+	fn metrics(&self) -> &'static [Metric] {
+		&[
+			Metric {
+				name: "pressure.cpu",
+				unit: "%",
+				description: "Time some or all tasks waited for the cpu, over 10s, 60s and 300s",
+			},
+			Metric {
+				name: "pressure.memory",
+				unit: "%",
+				description: "Time some or all tasks waited for memory, over 10s, 60s and 300s",
+			},
+			Metric {
+				name: "pressure.io",
+				unit: "%",
+				description: "Time some or all tasks waited for io, over 10s, 60s and 300s",
+			},
+			Metric {
+				name: "pressure.tracked",
+				unit: "%",
+				description: "Pressure of the cgroup of the tracked process, if it uses cgroup v2",
+			},
+		]
+	}
+
+	fn init(&mut self) {}
+
+	fn sample(&mut self, context: &Context, sample: &mut Sample) {
+		if self
+			.last_sample
+			.is_some_and(|last| last.elapsed() < PRESSURE_INTERVAL)
+		{
+			return;
+		}
+		self.last_sample = Some(Instant::now());
+
+		sample.pressure = Some(PressureSample {
+			system: read_resources(Path::new("/proc/pressure"), ""),
+			tracked: context.tracked.and_then(|pid| {
+				let path = read_cgroup(&pid.to_string())?;
+				let dir = CGROUP_ROOTS
+					.iter()
+					.map(|root| Path::new(root).join(path.trim_start_matches('/')))
+					.find(|dir| dir.join("cpu.pressure").exists())?;
+
+				Some(CgroupPressure {
+					path,
+					pressure: read_resources(&dir, ".pressure"),
+				})
+			}),
+		});
+	}
+}
+
+/// Reads the pressure of every resource from the files `cpu`, `memory` and `io` in `dir`,
+/// with `suffix` appended to their names.
+fn read_resources(dir: &Path, suffix: &str) -> ResourcePressure {
+	let read = |resource: &str| read_pressure(&dir.join(format!("{}{}", resource, suffix)));
+
+	ResourcePressure {
+		cpu: read("cpu"),
+		memory: read("memory"),
+		io: read("io"),
+	}
+}
+
+/// Reads a pressure file, formatted like this:
+///
+/// ```text
+/// some avg10=0.29 avg60=0.16 avg300=0.08 total=6889747
+/// full avg10=0.01 avg60=0.03 avg300=0.00 total=5543882
+/// ```
+///
+/// Older kernels leave out the `full` line for the cpu.
+fn read_pressure(path: &Path) -> Option<Pressure> {
+	let content = fs::read_to_string(path).ok()?;
+	let mut some = None;
+	let mut full = None;
+
+	for line in content.lines() {
+		let mut fields = line.split_whitespace();
+		let kind = fields.next();
+		let mut stall = Stall::default();
+		for (key, value) in fields.filter_map(|field| field.split_once('=')) {
+			let value = value.parse().unwrap_or_default();
+			match key {
+				"avg10" => stall.avg10 = value,
+				"avg60" => stall.avg60 = value,
+				"avg300" => stall.avg300 = value,
+				_ => (),
+			}
+		}
+
+		match kind {
+			Some("some") => some = Some(stall),
+			Some("full") => full = Some(stall),
+			_ => (),
+		}
+	}
+
+	Some(Pressure { some: some?, full })
+}
+
+/// Reads the cgroup v2 path of the process `pid` from its `0::/path` line in `/proc/<pid>/cgroup`.
+fn read_cgroup(pid: &str) -> Option<String> {
+	fs::read_to_string(Path::new("/proc").join(pid).join("cgroup"))
+		.ok()?
+		.lines()
+		.find_map(|line| line.strip_prefix("0::"))
+		.map(str::to_owned)
+}
@@ -0,0 +1,114 @@
+use std::time::Instant;
+
+use memu::units::MegaByte;
+use sysinfo::{ProcessExt, ProcessRefreshKind, RefreshKind, System, SystemExt};
+
+use super::{
+	collector::{Collector, Context, Metric},
+	per_second, Sample, TrackedData, TrackedSample,
+};
+use crate::terminal::Process;
+
+/// Collects all processes and the tracked process.
+///
+/// Processes are only collected every `sampling.processes`, the tracked process every time.
+pub struct ProcessCollector {
+	sys: System,
+	last_sample: Instant,
+	last_processes: Option<Instant>,
+}
+
+impl ProcessCollector {
+	pub fn new() -> Self {
+		ProcessCollector {
+			sys: System::new_with_specifics(RefreshKind::new()),
+			last_sample: Instant::now(),
+			last_processes: None,
+		}
+	}
+}
+
+impl Collector for ProcessCollector {
+	fn name(&self) -> &'static str {
+		"processes"
+	}
+
+	fn metrics(&self) -> &'static [Metric] {
+		&[
+			Metric {
+				name: "processes",
+				unit: "",
+				description:
+					"Pid, name, user, memory, cpu usage, run time and disk usage of every process",
+			},
+			Metric {
+				name: "tracked.cpu_usage",
+				unit: "%",
+				description: "Cpu usage of the tracked process",
+			},
+			Metric {
+				name: "tracked.memory",
+				unit: "MB",
+				description: "Memory used by the tracked process",
+			},
+			Metric {
+				name: "tracked.written",
+				unit: "KB/s",
+				description: "Data written to disk by the tracked process",
+			},
+			Metric {
+				name: "tracked.read",
+				unit: "KB/s",
+				description: "Data read from disk by the tracked process",
+			},
+		]
+	}
+
+	fn init(&mut self) {
+		self.sys.refresh_specifics(
+			RefreshKind::new()
+				.with_processes(ProcessRefreshKind::everything())
+				.with_users_list(),
+		);
+		self.last_sample = Instant::now();
+	}
+
+	fn sample(&mut self, context: &Context, sample: &mut Sample) {
+		let elapsed = self.last_sample.elapsed();
+		self.last_sample = Instant::now();
+
+		let processes_due = self
+			.last_processes
+			.is_none_or(|last| last.elapsed() >= context.config.sampling.processes);
+
+		if processes_due {
+			self.sys.refresh_processes();
+			self.last_processes = Some(Instant::now());
+		} else if let Some(pid) = context.tracked {
+			self.sys.refresh_process(pid);
+		}
+
+		sample.processes = processes_due.then(|| {
+			let mut processes: Vec<Process> = self
+				.sys
+				.processes()
+				.iter()
+				.map(|(pid, process)| Process::from_pp(pid, process, &self.sys))
+				.collect();
+			processes.sort_by(|a, b| a.name.cmp(&b.name));
+			processes
+		});
+
+		sample.tracked = context.tracked.map(|pid| TrackedSample {
+			pid,
+			process: self.sys.process(pid).map(|process| {
+				let disk_usage = process.disk_usage();
+				TrackedData {
+					process: Process::from_pp(&pid, process, &self.sys),
+					written: per_second(MegaByte::new(disk_usage.written_bytes), elapsed),
+					read: per_second(MegaByte::new(disk_usage.read_bytes), elapsed),
+				}
+			}),
+		});
+	}
+}
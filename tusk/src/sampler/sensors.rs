@@ -0,0 +1,68 @@
+use sysinfo::{ComponentExt, RefreshKind, System, SystemExt};
+
+use super::{
+	collector::{Collector, Context, Metric},
+	Sample, SensorSample,
+};
+
+/// Collects the temperature sensors the kernel exposes in `/sys/class/hwmon`.
+///
+/// The sensors are only listed once, listing them again would reset their highest temperature.
+pub struct SensorCollector {
+	sys: System,
+}
+
+impl SensorCollector {
+	pub fn new() -> Self {
+		SensorCollector {
+			sys: System::new_with_specifics(RefreshKind::new()),
+		}
+	}
+}
+
+impl Collector for SensorCollector {
+	fn name(&self) -> &'static str {
+		"sensors"
+	}
+
+	fn metrics(&self) -> &'static [Metric] {
+		&[
+			Metric {
+				name: "sensors.temperature",
+				unit: "°C",
+				description: "Current temperature of every sensor",
+			},
+			Metric {
+				name: "sensors.max",
+				unit: "°C",
+				description: "Highest temperature of every sensor reported by the kernel",
+			},
+			Metric {
+				name: "sensors.critical",
+				unit: "°C",
+				description: "Temperature at which the hardware of every sensor is in danger",
+			},
+		]
+	}
+
+	fn init(&mut self) {
+		self.sys.refresh_components_list();
+	}
+
+	fn sample(&mut self, _context: &Context, sample: &mut Sample) {
+		self.sys.refresh_components();
+
+		sample.sensors = Some(
+			self.sys
+				.components()
+				.iter()
+				.map(|component| SensorSample {
+					label: component.label().to_owned(),
+					temperature: component.temperature(),
+					max: component.max(),
+					critical: component.critical(),
+				})
+				.collect(),
+		);
+	}
+}
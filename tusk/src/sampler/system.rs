@@ -0,0 +1,155 @@
+use std::{
+	collections::HashSet,
+	fs,
+	time::{Duration, Instant},
+};
+
+use sysinfo::{RefreshKind, System, SystemExt};
+
+use super::{
+	collector::{Collector, Context, Metric},
+	Sample, SystemSample,
+};
+
+/// The time between two samples, often enough for the uptime and the process and thread counts.
+/// The kernel only updates the load averages every five seconds, so they repeat in between.
+const SYSTEM_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The file listing the current logins.
+const UTMP: &str = "/var/run/utmp";
+
+/// The size of a record in the utmp file, see `man 5 utmp`.
+const UTMP_RECORD: usize = 384;
+
+/// The type of a utmp record of a logged in user.
+const USER_PROCESS: i32 = 7;
+
+/// Collects the host, uptime, load averages, logged in users and the number of tasks.
+pub struct SystemCollector {
+	sys: System,
+	last_sample: Option<Instant>,
+}
+
+impl SystemCollector {
+	pub fn new() -> Self {
+		SystemCollector {
+			sys: System::new_with_specifics(RefreshKind::new()),
+			last_sample: None,
+		}
+	}
+}
+
+impl Collector for SystemCollector {
+	fn name(&self) -> &'static str {
+		"system"
+	}
+
+	fn metrics(&self) -> &'static [Metric] {
+		&[
+			Metric {
+				name: "system.host",
+				unit: "",
+				description: "Host name, kernel version and operating system",
+			},
+			Metric {
+				name: "system.uptime",
+				unit: "s",
+				description: "Time since the system booted",
+			},
+			Metric {
+				name: "system.load",
+				unit: "",
+				description: "Load averages over 1, 5 and 15 minutes",
+			},
+			Metric {
+				name: "system.users",
+				unit: "",
+				description: "Users logged in",
+			},
+			Metric {
+				name: "system.tasks",
+				unit: "",
+				description: "Number of processes and threads",
+			},
+		]
+	}
+
+	fn init(&mut self) {}
+
+	fn sample(&mut self, _context: &Context, sample: &mut Sample) {
+		if self
+			.last_sample
+			.is_some_and(|last| last.elapsed() < SYSTEM_INTERVAL)
+		{
+			return;
+		}
+		self.last_sample = Some(Instant::now());
+
+		let os = self.sys.name().map(|name| match self.sys.os_version() {
+			Some(version) => format!("{} {}", name, version),
+			None => name,
+		});
+
+		sample.system = Some(SystemSample {
+			host_name: self.sys.host_name(),
+			kernel: self.sys.kernel_version(),
+			os,
+			boot_time: self.sys.boot_time(),
+			uptime: Duration::from_secs(self.sys.uptime()),
+			load: self.sys.load_average(),
+			users: count_users(),
+			processes: count_processes(),
+			threads: count_threads(),
+		});
+	}
+}
+
+/// Counts the distinct users with a login in the utmp file, 0 if it can't be read.
+fn count_users() -> usize {
+	let Ok(utmp) = fs::read(UTMP) else {
+		return 0;
+	};
+
+	utmp.chunks_exact(UTMP_RECORD)
+		.filter(|record| {
+			i32::from_ne_bytes([record[0], record[1], record[2], record[3]]) == USER_PROCESS
+		})
+		.map(|record| {
+			// The user name is at offset 44, padded with zeros to 32 bytes.
+			let name = &record[44..76];
+			let end = name
+				.iter()
+				.position(|&byte| byte == 0)
+				.unwrap_or(name.len());
+			&name[..end]
+		})
+		.filter(|name| !name.is_empty())
+		.collect::<HashSet<_>>()
+		.len()
+}
+
+/// Counts the processes, which are the numbered directories in `/proc`.
+fn count_processes() -> usize {
+	fs::read_dir("/proc").map_or(0, |entries| {
+		entries
+			.filter_map(Result::ok)
+			.filter(|entry| {
+				entry
+					.file_name()
+					.to_str()
+					.is_some_and(|name| name.bytes().all(|byte| byte.is_ascii_digit()))
+			})
+			.count()
+	})
+}
+
+/// Reads the number of threads from `/proc/loadavg`, formatted like `0.10 0.23 0.20 1/71 4022`.
+fn count_threads() -> usize {
+	fs::read_to_string("/proc/loadavg")
+		.ok()
+		.and_then(|loadavg| {
+			let tasks = loadavg.split_whitespace().nth(3)?;
+			tasks.split_once('/')?.1.parse().ok()
+		})
+		.unwrap_or_default()
+}
@@ -1,24 +1,151 @@
-use std::time::Duration;
+use std::{collections::VecDeque, time::Duration};
 
-use memu::units::{KiloByte, MegaByte};
-use sysinfo::{Cpu, CpuExt, NetworkExt, Networks, NetworksExt};
+use memu::{
+	constants::{BITS, GIGABYTE, KILOBYTE, MEGABYTE, TERABYTE},
+	units::{Byte, GigaByte, KiloByte, MegaByte, TeraByte},
+};
+use ratatui::layout::Rect;
 
-pub fn compute_in(networks: &Networks) -> MegaByte {
-	MegaByte::new(networks.iter().map(|(_, n)| n.received()).sum())
+use crate::config::datapoints::RateUnit;
+
+/// The bars of a history column, from the lowest to the highest value.
+pub const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Drops the oldest datapoints until at most `len` are left.
+pub fn truncate_front<T>(data: &mut VecDeque<T>, len: usize) {
+	if data.len() > len {
+		data.drain(..data.len() - len);
+	}
+}
+
+/// Picks the largest unit that `bytes` is at least one of, returning its size in bytes and name.
+pub fn byte_unit(bytes: f64) -> (f64, &'static str) {
+	[
+		(TERABYTE, TeraByte::UNIT),
+		(GIGABYTE, GigaByte::UNIT),
+		(MEGABYTE, MegaByte::UNIT),
+		(KILOBYTE, KiloByte::UNIT),
+	]
+	.into_iter()
+	.find(|&(size, _)| bytes >= size as f64)
+	.map_or((1.0, Byte::UNIT), |(size, unit)| (size as f64, unit))
+}
+
+/// Formats `bytes` in the largest fitting unit, like `1.50MB`.
+pub fn format_bytes(bytes: Byte) -> String {
+	let (size, unit) = byte_unit(bytes.as_f64());
+	format!("{:.2}{}", bytes.as_f64() / size, unit)
+}
+
+/// Picks the unit fitting a rate of `bytes` per second best, returning its size in bytes and its
+/// name including `/s`.
+///
+/// Bits use decimal prefixes like network speeds are advertised.
+pub fn rate_unit(bytes: f64, unit: RateUnit) -> (f64, String) {
+	match unit {
+		RateUnit::Bytes => {
+			let (size, name) = byte_unit(bytes);
+			(size, format!("{}/s", name))
+		},
+		RateUnit::Bits => {
+			let bits = bytes * BITS as f64;
+			let (size, name) = [(1e12, "Tb"), (1e9, "Gb"), (1e6, "Mb"), (1e3, "Kb")]
+				.into_iter()
+				.find(|&(size, _)| bits >= size)
+				.unwrap_or((1.0, "b"));
+			(size / BITS as f64, format!("{}/s", name))
+		},
+	}
 }
 
-pub fn compute_out(networks: &Networks) -> MegaByte {
-	MegaByte::new(networks.iter().map(|(_, n)| n.transmitted()).sum())
+/// Formats a rate of `bytes` per second in the unit fitting it best, like `1.50MB/s`.
+pub fn format_rate(bytes: Byte, unit: RateUnit) -> String {
+	let (size, name) = rate_unit(bytes.as_f64(), unit);
+	format!("{:.2}{}", bytes.as_f64() / size, name)
 }
 
-pub fn per_second(data: MegaByte, elapsed: Duration) -> KiloByte {
-	KiloByte::from(data.as_f64() / elapsed.as_secs_f64())
+/// Gets the width left in `area` for a history column after the columns of `fixed_widths`.
+///
+/// Borders and the spacing between columns take up space as well.
+pub fn history_width(area: Rect, fixed_widths: &[u16]) -> u16 {
+	area.width
+		.saturating_sub(fixed_widths.iter().sum::<u16>() + 2 + fixed_widths.len() as u16)
 }
 
-pub fn compute_frequency(vec: &[Cpu]) -> u64 {
-	vec.iter().map(|core| core.frequency()).sum::<u64>() / vec.len() as u64
+/// Formats `duration` in days, hours and minutes, like `3d 4h 12m`.
+pub fn format_uptime(duration: Duration) -> String {
+	let minutes = duration.as_secs() / 60;
+	let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+
+	if days > 0 {
+		format!("{}d {}h {}m", days, hours, minutes)
+	} else {
+		format!("{}h {}m", hours, minutes)
+	}
+}
+
+/// Formats seconds since the unix epoch as a date and time in utc, like `2023-10-05 14:30 UTC`.
+pub fn format_date(seconds: u64) -> String {
+	let days = (seconds / 86_400) as i64;
+	let minutes = seconds % 86_400 / 60;
+
+	// Converts days to a date of the gregorian calendar, counting in eras of 400 years starting
+	// on the 1st of March so leap days are the last day of a year.
+	let z = days + 719_468;
+	let era = z.div_euclid(146_097);
+	let day_of_era = z.rem_euclid(146_097);
+	let year_of_era =
+		(day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 {
+		month_index + 3
+	} else {
+		month_index - 9
+	};
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+	format!(
+		"{:04}-{:02}-{:02} {:02}:{:02} UTC",
+		year,
+		month,
+		day,
+		minutes / 60,
+		minutes % 60
+	)
+}
+
+/// Makes the first letter of `s` uppercase.
+pub fn capitalize(s: &str) -> String {
+	let mut chars = s.chars();
+	match chars.next() {
+		Some(first) => first.to_uppercase().chain(chars).collect(),
+		None => String::new(),
+	}
 }
 
-pub fn compute_usage(vec: &[Cpu]) -> f32 {
-	vec.iter().map(|core| core.cpu_usage()).sum::<f32>() / vec.len() as f32
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn dates() {
+		for (seconds, date) in [
+			(0, "1970-01-01 00:00 UTC"),
+			(59, "1970-01-01 00:00 UTC"),
+			(94_694_340, "1972-12-31 23:59 UTC"),
+			(951_825_600, "2000-02-29 12:00 UTC"),
+			(1_704_067_140, "2023-12-31 23:59 UTC"),
+			(1_704_067_200, "2024-01-01 00:00 UTC"),
+			(1_709_164_800, "2024-02-29 00:00 UTC"),
+			(1_709_251_140, "2024-02-29 23:59 UTC"),
+			(1_709_251_200, "2024-03-01 00:00 UTC"),
+			// 2100 is no leap year.
+			(4_107_542_400 - 86_400, "2100-02-28 00:00 UTC"),
+			(4_107_542_400, "2100-03-01 00:00 UTC"),
+		] {
+			assert_eq!(format_date(seconds), date);
+		}
+	}
 }
@@ -1,69 +1,181 @@
 use std::io;
-use std::sync::Arc;
 
 use namefn::namefn;
 use ratatui::{prelude::*, widgets::*};
 
 use ratatui::widgets::Tabs as TabWidget;
 
-use crate::config::theme::THEME;
+use crate::config::{
+	self,
+	keys::{Action, Mode},
+	theme,
+};
 
-use super::tabs::debug::window_debug;
-use super::tabs::default::window_default;
-use super::tabs::processes::window_processes;
-use super::tabs::tracked::window_tracked;
-use super::{App, Screen, TopBar};
+use super::app_util::capitalize;
+use super::layout::draw_layout;
+use super::{App, Dialog, Prompt, StatusLevel, TopBar};
+use crate::control::Signal;
 
 /// Wrapper function for drawing terminals
 #[namefn]
 pub fn draw<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
-	app.log(format!("Drawing {:?}", app.current_tab()), NAME);
+	app.log(format!("Drawing {:?}", app.current_tab().screen), NAME);
 	terminal.draw(|f| ui(f, app))?;
 	Ok(())
 }
 
 fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
 	let size = f.size();
+	let status_height = if app.status.is_some() { 1 } else { 0 };
 	let chunks = Layout::default()
-		.constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+		.constraints(
+			[
+				Constraint::Length(3),
+				Constraint::Min(0),
+				Constraint::Length(status_height),
+			]
+			.as_ref(),
+		)
 		.margin(1)
 		.split(size);
 
 	match app.top_bar {
 		TopBar::Tabs => draw_tabs(f, app, chunks[0]),
-		TopBar::Input => draw_input(f, app, chunks[0]),
+		TopBar::Input(prompt) => draw_input(f, app, prompt, chunks[0]),
 	}
 
-	match app.current_tab() {
-		Screen::Default => window_default(f, app, chunks[1]),
-		Screen::Processes => window_processes(f, app, chunks[1]),
-		Screen::Tracked => window_tracked(f, app, chunks[1]),
-		Screen::Debug => window_debug(f, app, chunks[1]),
+	// The process table keeps its selection and scroll offset in `app` while drawing.
+	let layout = app.current_tab().layout.clone();
+	draw_layout(f, app, &layout, chunks[1]);
+
+	draw_status(f, app, chunks[2]);
+
+	if let Some(dialog) = &app.dialog {
+		draw_dialog(f, dialog, size);
+	}
+}
+
+/// Draws `dialog` in the middle of `area`, above everything else.
+fn draw_dialog<B: Backend>(f: &mut Frame<B>, dialog: &Dialog, area: Rect) {
+	let theme = theme::current();
+	let config = config::current();
+	let hint = |action: Action| {
+		config
+			.keys
+			.keys(Mode::Dialog, action)
+			.map(ToString::to_string)
+			.collect::<Vec<_>>()
+			.join("/")
+	};
+	let hint = Line::styled(
+		format!(
+			"{} to confirm, {} to cancel",
+			hint(Action::Confirm),
+			hint(Action::Cancel)
+		),
+		theme.axis,
+	);
+
+	let (title, mut text, selected) = match dialog {
+		Dialog::Signals { target, selected } => {
+			let mut text: Vec<Line> = Signal::ALL
+				.iter()
+				.map(|signal| Line::from(format!("{:<8} {}", signal.name(), signal.description())))
+				.collect();
+			text.push(Line::default());
+			text.push(hint);
+			(
+				format!("Send a signal to {}", target),
+				text,
+				Some(*selected),
+			)
+		},
+		Dialog::Confirm { target, operation } => {
+			let question = format!(
+				"{}?",
+				capitalize(&operation.describe(&target.to_string(), false))
+			);
+			let text = vec![Line::from(question), Line::default(), hint];
+			(String::from("Confirm"), text, None)
+		},
+	};
+
+	let width = text
+		.iter()
+		.map(Line::width)
+		.chain([title.chars().count()])
+		.max()
+		.unwrap_or_default() as u16
+		+ 4;
+	let height = text.len() as u16 + 2;
+	let area = Rect {
+		x: area.x + area.width.saturating_sub(width) / 2,
+		y: area.y + area.height.saturating_sub(height) / 2,
+		width: width.min(area.width),
+		height: height.min(area.height),
+	};
+
+	if let Some(line) = selected.and_then(|selected| text.get_mut(selected)) {
+		line.patch_style(theme.selected_text);
+	}
+
+	f.render_widget(Clear, area);
+	f.render_widget(
+		Paragraph::new(text).style(theme.text).block(
+			Block::default()
+				.borders(Borders::ALL)
+				.title(title.bold())
+				.padding(Padding::horizontal(1))
+				.style(theme.window),
+		),
+		area,
+	);
+}
+
+/// Draws the status line, if there is a status to display.
+fn draw_status<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+
+	if let Some(status) = &app.status {
+		let style = match status.level {
+			StatusLevel::Info => theme.text,
+			StatusLevel::Error => theme.error,
+		};
+
+		let line = Paragraph::new(Line::from(Span::styled(status.message.as_str(), style)));
+		f.render_widget(line, area);
 	}
 }
 
-/// Draws the input top bar.
-fn draw_input<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
-	let theme = Arc::clone(&THEME);
+/// Draws the input top bar, titled with the prompt or why the typed search is invalid.
+fn draw_input<B: Backend>(f: &mut Frame<B>, app: &mut App, prompt: Prompt, area: Rect) {
+	let theme = theme::current();
 
+	let title = match &app.search_error {
+		Some(e) if prompt == Prompt::Search => {
+			Span::styled(format!("{}: {}", prompt.title(), e), theme.error)
+		},
+		_ => Span::raw(prompt.title()),
+	};
 	let input = Paragraph::new(app.input.as_str()).style(theme.text).block(
 		Block::default()
 			.borders(Borders::ALL)
-			.title("Input")
+			.title(title)
 			.style(theme.window),
 	);
 	f.render_widget(input, area);
-	f.set_cursor(area.x + app.input_position as u16 + 1, area.y + 1)
+	let cursor = app.input[..app.input_position].chars().count();
+	f.set_cursor(area.x + cursor as u16 + 1, area.y + 1)
 }
 
 /// Draws the tabs top bar.
 fn draw_tabs<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
-	let theme = Arc::clone(&THEME);
+	let theme = theme::current();
 
 	let titles = app
-		.tabs
+		.tabs()
 		.iter()
-		.map(|t| text::Line::from(Span::styled(t.as_string(), theme.tab)))
+		.map(|t| text::Line::from(Span::styled(t.title(), theme.tab)))
 		.collect();
 
 	let tabs = TabWidget::new(titles)
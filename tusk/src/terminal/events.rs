@@ -1,10 +1,19 @@
 use crossterm::event::{
-	Event::{self, Key},
-	KeyCode::{BackTab, Backspace, Char, Enter, Esc, Left, Right, Tab, F},
+	Event::{self, Key as KeyEvent, Mouse},
+	KeyCode::{Down, Left, Right, Up},
+	MouseEvent, MouseEventKind,
 };
 use namefn::namefn;
 
-use super::{App, TopBar};
+use crate::config::{
+	keys::{Action, Key, Lookup, Mode},
+	layout::Module,
+};
+
+use super::{App, Prompt, TopBar};
+
+/// The number of processes the selection moves per step of the mouse wheel.
+const WHEEL_STEP: isize = 3;
 
 /// Controls the flow of the app, based on user inputs.
 pub enum ControlFlow {
@@ -17,35 +26,112 @@ pub enum ControlFlow {
 }
 
 /// Handeles user input events.
+///
+/// Keys are collected until they match a binding of the current mode. Keys that don't lead
+/// to any binding are typed while in the input, otherwise they are dropped.
 #[namefn]
 pub fn handle_event(event: Event, app: &mut App) -> ControlFlow {
-	if let Key(key) = event {
+	if let KeyEvent(event) = event {
 		app.log("Key event", NAME);
 
-		if app.top_bar == TopBar::Input {
-			match key.code {
-				Char(ch) => app.input_type(ch),
-				Backspace => app.input_backspace(),
-				Left | Right => app.arrow_event(key.code),
-				Enter => app.input_enter(),
-				Esc => app.wipe_input(),
-				Tab => app.inc_tabs_index(),
-				BackTab => app.dec_tabs_index(),
-				_ => (),
-			}
-		} else {
-			match key.code {
-				Char('q') => return ControlFlow::Quit,
-				Char('r') => return ControlFlow::Reload,
-				Char('i') => app.top_bar = TopBar::Input,
-				Tab => app.inc_tabs_index(),
-				BackTab => app.dec_tabs_index(),
-				#[cfg(debug_assertions)]
-				Char('p') => panic!("intentional debug panic"),
-				F(1) => app.switch_debug(),
-				_ => return ControlFlow::Continue,
-			}
+		let mode = match (&app.dialog, app.top_bar) {
+			(Some(_), _) => Mode::Dialog,
+			(None, TopBar::Tabs) => Mode::Normal,
+			(None, TopBar::Input(_)) => Mode::Input,
+		};
+
+		let key = Key::from(event);
+		app.pending_keys.push(key);
+
+		match app.config.keys.lookup(mode, &app.pending_keys) {
+			Lookup::Action(action) => {
+				app.pending_keys.clear();
+				return perform(action, app);
+			},
+			Lookup::Pending => {
+				app.log(format!("Waiting for more keys after `{}`", key), NAME);
+			},
+			Lookup::None => {
+				let pending: Vec<Key> = app.pending_keys.drain(..).collect();
+				let earlier = &pending[..pending.len() - 1];
+
+				if mode == Mode::Input {
+					earlier
+						.iter()
+						.filter_map(Key::char)
+						.for_each(|ch| app.input_type(ch));
+				}
+
+				// The last key may start a new binding on its own.
+				if !earlier.is_empty() {
+					return handle_event(Event::Key(event), app);
+				}
+
+				if let (Mode::Input, Some(ch)) = (mode, key.char()) {
+					app.input_type(ch);
+				}
+			},
 		}
 	}
+
+	// The wheel only scrolls the process table while it is shown and nothing is in front of it.
+	if let Mouse(MouseEvent { kind, .. }) = event {
+		if app.dialog.is_some() || !app.current_tab().layout.contains(Module::Processes) {
+			return ControlFlow::Continue;
+		}
+
+		match kind {
+			MouseEventKind::ScrollUp => app.move_selection(-WHEEL_STEP),
+			MouseEventKind::ScrollDown => app.move_selection(WHEEL_STEP),
+			_ => (),
+		}
+	}
+	ControlFlow::Continue
+}
+
+/// Performs the given action.
+#[namefn]
+fn perform(action: Action, app: &mut App) -> ControlFlow {
+	app.log(format!("Action {:?}", action), NAME);
+
+	match action {
+		Action::Quit => return ControlFlow::Quit,
+		Action::Reload => return ControlFlow::Reload,
+		Action::NextTab => app.inc_tabs_index(),
+		Action::PreviousTab => app.dec_tabs_index(),
+		Action::StartTrackInput => app.start_input(Prompt::Track),
+		Action::StartSearch => app.start_input(Prompt::Search),
+		Action::SaveView => app.start_input(Prompt::View),
+		Action::SendSignal => app.open_signals(),
+		Action::Renice => app.start_input(Prompt::Nice),
+		Action::SetIoPriority => app.start_input(Prompt::IoPriority),
+		Action::SetAffinity => app.start_input(Prompt::Affinity),
+		Action::ToggleDebug => app.switch_debug(),
+		Action::ToggleCpuView => app.toggle_cpu_view(),
+		Action::SelectUp if app.dialog.is_some() => app.move_dialog_selection(-1),
+		Action::SelectDown if app.dialog.is_some() => app.move_dialog_selection(1),
+		Action::SelectUp => app.move_selection(-1),
+		Action::SelectDown => app.move_selection(1),
+		Action::SelectPageUp => app.move_selection(-(app.process_page as isize)),
+		Action::SelectPageDown => app.move_selection(app.process_page as isize),
+		Action::SelectFirst => app.move_selection(isize::MIN),
+		Action::SelectLast => app.move_selection(isize::MAX),
+		Action::SortNext => app.sort_by(app.process_sort.column.next()),
+		Action::SortPrevious => app.sort_by(app.process_sort.column.previous()),
+		Action::ToggleSortOrder => app.toggle_sort_order(),
+		Action::ToggleProcessTree => app.toggle_process_tree(),
+		Action::ToggleCollapsed => app.toggle_collapsed(),
+		Action::SelectParent => app.select_parent(),
+		Action::ClearStatus => app.clear_status(),
+		Action::Confirm if app.dialog.is_some() => app.confirm_dialog(),
+		Action::Cancel if app.dialog.is_some() => app.cancel_dialog(),
+		Action::Confirm => app.input_enter(),
+		Action::Cancel => app.cancel_input(),
+		Action::DeleteBackward => app.input_backspace(),
+		Action::CursorLeft => app.arrow_event(Left),
+		Action::CursorRight => app.arrow_event(Right),
+		Action::CursorStart => app.arrow_event(Up),
+		Action::CursorEnd => app.arrow_event(Down),
+	}
 	ControlFlow::Continue
 }
@@ -0,0 +1,113 @@
+use ratatui::prelude::*;
+
+use crate::config::layout::{Content, Module, Node};
+
+use super::{
+	modules::{
+		cpu_frequency::draw_frequency,
+		cpu_usage::draw_usage,
+		debug::{
+			log::draw_log,
+			tick::{draw_tick_parts, draw_ticks},
+		},
+		disk::{draw_devices, draw_disk_io, draw_filesystems},
+		error::{draw_error, FrameError::MissingTracked},
+		memory::draw_memory,
+		network::{draw_interfaces, draw_network_in, draw_network_out},
+		pressure::draw_pressure,
+		processes::draw_processes,
+		sensors::draw_sensors,
+		stats::draw_stats,
+		tracked::{
+			cpu_usage::draw_tracked_usage, info::draw_tracked_info, memory::draw_tracked_memory,
+		},
+	},
+	App,
+};
+
+/// Draws `node` and all of its children in the given area.
+///
+/// Nodes only showing the tracked process are replaced by a single error if no process is tracked.
+pub fn draw_layout<B: Backend>(f: &mut Frame<B>, app: &mut App, node: &Node, area: Rect) {
+	if app.tracked.is_none() && node.is_tracked() {
+		draw_error(f, MissingTracked, area);
+		return;
+	}
+
+	let (direction, children) = match &node.content {
+		Content::Module(module) => return draw_module(f, app, *module, area),
+		Content::Row(children) => (Direction::Horizontal, children),
+		Content::Column(children) => (Direction::Vertical, children),
+	};
+
+	let total = match direction {
+		Direction::Horizontal => area.width,
+		Direction::Vertical => area.height,
+	};
+
+	let chunks = Layout::default()
+		.direction(direction)
+		.constraints(constraints(children, total))
+		.split(area);
+
+	for (child, chunk) in children.iter().zip(chunks.iter()) {
+		draw_layout(f, app, child, *chunk);
+	}
+}
+
+/// Computes the constraints of `children`, nodes without a size share the remaining space.
+fn constraints(children: &[Node], total: u16) -> Vec<Constraint> {
+	let sized: u16 = children
+		.iter()
+		.filter_map(|child| child.size)
+		.fold(0, |sum, size| sum.saturating_add(size.length(total)));
+	let without_size = children.iter().filter(|child| child.size.is_none()).count() as u16;
+	let share = total.saturating_sub(sized) / without_size.max(1);
+
+	children
+		.iter()
+		.map(|child| Constraint::Length(child.size.map_or(share, |size| size.length(total))))
+		.collect()
+}
+
+/// Draws a single module.
+fn draw_module<B: Backend>(f: &mut Frame<B>, app: &mut App, module: Module, area: Rect) {
+	match module {
+		Module::Stats => draw_stats(f, app, area),
+		Module::CpuUsage => draw_usage(f, app, area),
+		Module::CpuFrequency => draw_frequency(f, app, area),
+		Module::Memory => draw_memory(f, app, area),
+		Module::DiskIo => draw_disk_io(f, app, area),
+		Module::DiskDevices => draw_devices(f, app, area),
+		Module::DiskFilesystems => draw_filesystems(f, app, area),
+		Module::Network => {
+			let chunks = Layout::default()
+				.direction(Direction::Horizontal)
+				.constraints(
+					[
+						Constraint::Ratio(1, 4),
+						Constraint::Ratio(1, 4),
+						Constraint::Ratio(1, 2),
+					]
+					.as_ref(),
+				)
+				.split(area);
+
+			draw_network_in(f, app, chunks[0]);
+			draw_network_out(f, app, chunks[1]);
+			draw_interfaces(f, app, chunks[2]);
+		},
+		Module::NetworkIn => draw_network_in(f, app, area),
+		Module::NetworkOut => draw_network_out(f, app, area),
+		Module::NetworkInterfaces => draw_interfaces(f, app, area),
+		Module::Sensors => draw_sensors(f, app, area),
+		Module::Pressure => draw_pressure(f, app, area),
+		Module::Processes => draw_processes(f, app, area),
+		Module::TrackedInfo => draw_tracked_info(f, app, area),
+		Module::TrackedCpuUsage => draw_tracked_usage(f, app, area),
+		Module::TrackedMemory => draw_tracked_memory(f, app, area),
+		Module::DebugLog => draw_log(f, app, area),
+		Module::DebugTicks => draw_ticks(f, app, area),
+		Module::DebugTickParts => draw_tick_parts(f, app, area),
+	}
+}
@@ -4,60 +4,102 @@ mod app_util;
 pub(super) mod draw;
 /// Wrapper functions for handling events.
 pub(super) mod events;
+/// Draws the layout of a tab.
+mod layout;
 /// This contains the windows drawn on the terminal.
 mod modules;
-/// This contains wrapper function for drawing tabs.
-mod tabs;
+/// Nests processes under their parents.
+mod process_tree;
 
 use std::{
-	collections::VecDeque,
+	collections::{HashSet, VecDeque},
+	fmt::Display,
+	io::ErrorKind,
 	str::FromStr,
-	time::{Duration, Instant},
+	sync::Arc,
+	time::Duration,
 };
 
 use crossterm::event::KeyCode::{self, Down, Left, Right, Up};
 use log::error;
-use memu::{
-	units::{KiloByte, MegaByte},
-	MemoryUnit,
-};
+use memu::units::{Byte, KiloByte, MegaByte};
 use namefn::namefn;
-use ratatui::widgets::ScrollbarState;
-use sysinfo::{CpuExt, Pid, PidExt, Process as Proc, ProcessExt, ProcessStatus, System, SystemExt};
+use ratatui::widgets::{ScrollbarState, TableState};
+use serde::{Deserialize, Serialize};
+use sysinfo::{
+	LoadAvg, Pid, PidExt, Process as Proc, ProcessExt, ProcessStatus, System, SystemExt, UserExt,
+};
 
 use crate::{
-	config::theme::Theme,
-	datapoints::{
-		CPU_USAGE_DATAPOINTS, DEBUG_TICK_DATAPOINTS, LOG_MESSAGES, NETWORK_DATAPOINTS,
-		TRACKED_LOG_EVENTS, TRACKED_PROCESS_DATAPOINTS,
+	config::{
+		self,
+		filter::Filter,
+		keys::Key,
+		layout::{CpuView, ProcessSort, SortColumn},
+		tabs::Tab,
+		Config, View,
+	},
+	control::{self, Operation, Signal},
+	sampler::{
+		CgroupPressure, CpuTimes, DeviceSample, FilesystemSample, InterfaceSample, MemorySample,
+		ResourcePressure, Sample, Scaling, SensorSample, SystemSample, TrackedData, TrackedSample,
 	},
-	TABS,
 };
 
-use self::app_util::{compute_frequency, compute_in, compute_out, compute_usage, per_second};
+use self::{
+	app_util::{capitalize, truncate_front},
+	process_tree::TreeRow,
+};
 
 /// Defines which screen is drawn.
-#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
 pub enum Screen {
 	/// The default screen, a bit of everything.
 	#[default]
 	Default,
 	/// A special process screen with process information.
+	#[serde(alias = "process")]
 	Processes,
+	/// Block devices and filesystems.
+	Disks,
 	/// Tracked process screen
 	Tracked,
 	/// A debug screen only for developers. Enable by pressing
 	Debug,
+	/// A screen showing only the modules of its tabs layout.
+	Custom,
 }
 
 impl Screen {
 	/// Gets the screens name, used for displaying.
-	fn as_string(&self) -> &str {
+	pub fn as_string(&self) -> &'static str {
 		match *self {
 			Self::Default => "Default",
 			Self::Processes => "Process",
+			Self::Disks => "Disks",
 			Self::Tracked => "Tracked",
 			Self::Debug => "Debug",
+			Self::Custom => "Custom",
+		}
+	}
+}
+
+impl FromStr for Screen {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"default" => Ok(Self::Default),
+			"processes" | "process" => Ok(Self::Processes),
+			"disks" | "disk" => Ok(Self::Disks),
+			"tracked" => Ok(Self::Tracked),
+			"debug" => Ok(Self::Debug),
+			"custom" => Ok(Self::Custom),
+			_ => Err(format!(
+				"unknown screen `{}`, expected one of default, processes, disks, tracked, debug or custom",
+				s
+			)),
 		}
 	}
 }
@@ -69,38 +111,191 @@ pub enum TopBar {
 	#[default]
 	Tabs,
 	/// Display input in the top bar.
-	Input,
+	Input(Prompt),
+}
+
+/// What the input is asking for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Prompt {
+	/// The pid of the process to track.
+	Track,
+	/// The filter searching the processes, applied while typing.
+	Search,
+	/// The name the current search is saved as.
+	View,
+	/// The nice value of the selected process.
+	Nice,
+	/// The io priority of the selected process.
+	IoPriority,
+	/// The cores the selected process may run on.
+	Affinity,
+}
+
+impl Prompt {
+	/// Gets the title of the input.
+	pub fn title(&self) -> &'static str {
+		match self {
+			Self::Track => "Track pid",
+			Self::Search => "Search",
+			Self::View => "Save view as",
+			Self::Nice => "Nice value from -20 to 19",
+			Self::IoPriority => "Io priority: realtime 0-7, best-effort 0-7, idle or none",
+			Self::Affinity => "Cpu affinity, like 0-3,6",
+		}
+	}
+}
+
+/// The process a dialog or input changes.
+#[derive(Clone, Debug)]
+pub struct Target {
+	pub pid: u32,
+	pub name: String,
+}
+
+impl Display for Target {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} ({})", self.name, self.pid)
+	}
+}
+
+/// A dialog drawn above everything else, it takes all keys until it is closed.
+#[derive(Clone, Debug)]
+pub enum Dialog {
+	/// Picks the signal sent to `target`, `selected` indexes [`Signal::ALL`].
+	Signals { target: Target, selected: usize },
+	/// Asks whether to apply `operation` to `target`.
+	Confirm {
+		target: Target,
+		operation: Operation,
+	},
+}
+
+/// How important a status message is.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusLevel {
+	/// Something happened the user should know about.
+	Info,
+	/// Something went wrong.
+	Error,
+}
+
+/// A message displayed in the status line.
+pub struct Status {
+	pub message: String,
+	pub level: StatusLevel,
+}
+
+impl Status {
+	/// Creates an informational status message.
+	pub fn info<T: Into<String>>(message: T) -> Self {
+		Status {
+			message: message.into(),
+			level: StatusLevel::Info,
+		}
+	}
+
+	/// Creates an error status message.
+	pub fn error<T: Into<String>>(message: T) -> Self {
+		Status {
+			message: message.into(),
+			level: StatusLevel::Error,
+		}
+	}
 }
 
 /// The main app struct handeling all app relevent information.
 pub struct App {
-	sys: System,
+	/// The configuration this app was created with.
+	pub config: Arc<Config>,
 	pub log: VecDeque<Log>,
-	pub theme: Theme,
+	/// The scrollbar of the process table.
 	pub programs_scroll_state: ScrollbarState,
+	/// The selected row and scroll offset of the process table.
+	pub process_table: TableState,
+	/// The pid of the selected process, the selection follows it when the list changes.
+	selected_pid: Option<u32>,
+	/// The number of processes the process table showed when it was last drawn.
+	pub process_page: usize,
+	/// How the processes are currently sorted.
+	pub process_sort: ProcessSort,
+	/// Whether processes are nested under their parents.
+	pub process_tree: bool,
+	/// The pids of the processes whose children are hidden in the tree.
+	collapsed: HashSet<u32>,
+	/// Lists only the processes passing this filter in addition to the filter of the tab.
+	pub search: Option<Filter>,
+	/// Why the typed search can't be parsed, the last valid one stays active meanwhile.
+	pub search_error: Option<String>,
+	/// The search before the search input was opened, restored if it is cancelled.
+	search_before: Option<Filter>,
+	/// The open dialog, if any.
+	pub dialog: Option<Dialog>,
+	/// The process changed by the value typed into the input.
+	input_target: Option<Target>,
+	/// The message displayed in the status line, if any.
+	pub status: Option<Status>,
 	tabs_index: usize,
-	tabs: Vec<Screen>,
+	tabs: Vec<Tab>,
 	top_bar: TopBar,
 	input: String,
+	/// The cursor in `input`, as a byte offset on a char boundary.
 	input_position: usize,
+	/// The keys pressed so far of an unfinished key sequence.
+	pending_keys: Vec<Key>,
 	pub cpu_name: String,
 	pub cpu_frequency: u64,
 	pub cpu_usage: VecDeque<f32>,
-	pub network_out: VecDeque<KiloByte>,
-	pub network_in: VecDeque<KiloByte>,
+	/// The usage history of every core.
+	pub cpu_cores: Vec<VecDeque<f32>>,
+	/// The frequency history of every core.
+	pub cpu_frequencies: Vec<VecDeque<u64>>,
+	/// The frequency scaling settings of every core.
+	pub cpu_scaling: Vec<Scaling>,
+	/// The share of time all cores spent in every state, one datapoint per second.
+	pub cpu_times: VecDeque<CpuTimes>,
+	/// The newest share of time every core spent in every state.
+	pub core_times: Vec<CpuTimes>,
+	/// Context switches per second.
+	pub context_switches: f64,
+	/// Interrupts per second.
+	pub interrupts: f64,
+	/// How the cpu usage is currently shown.
+	pub cpu_view: CpuView,
+	pub memory: VecDeque<MemorySample>,
+	/// Bytes read per second from all block devices.
+	pub disk_read: VecDeque<Byte>,
+	/// Bytes written per second to all block devices.
+	pub disk_written: VecDeque<Byte>,
+	pub devices: Vec<DeviceSample>,
+	pub filesystems: Vec<FilesystemSample>,
+	/// Bytes transmitted per second.
+	pub network_out: VecDeque<Byte>,
+	/// Bytes received per second.
+	pub network_in: VecDeque<Byte>,
+	/// Every sampled network interface, sorted by name.
+	pub interfaces: Vec<Interface>,
+	/// The newest summary of the system, `None` until it was sampled.
+	pub system: Option<SystemSample>,
+	/// The load averages, one datapoint per second.
+	pub load: VecDeque<LoadAvg>,
+	/// Every temperature sensor, sorted by label.
+	pub sensors: Vec<Sensor>,
+	/// The pressure of the system, one datapoint every two seconds.
+	pub pressure: VecDeque<ResourcePressure>,
+	/// The newest pressure of the cgroup of the tracked process.
+	pub tracked_pressure: Option<CgroupPressure>,
 	pub processes: Vec<Process>,
 	tracked_pid: Option<Pid>,
+	/// Tracks the first process whose name contains this pattern, once it is found.
+	tracked_name: Option<String>,
 	pub tracked: Option<TrackedProcess>,
-	last_snapshot: Instant,
 	/// Staches the current working tick time to ensure all ticks display the same refresh.
 	working_prior: Duration,
 	/// How long the data and drawing took to process.
 	pub working_tick: VecDeque<Duration>,
 	/// How long the tick really took
 	pub real_tick: VecDeque<Duration>,
-	/// Staches the current fetiching tick time to ensure all ticks display the same refresh.
-	refresh_prior: Duration,
-	/// Data refresh time per tick.
+	/// Time taken by each sample.
 	pub refresh_tick: VecDeque<Duration>,
 	/// Drawing time per tick.
 	pub drawing_tick: VecDeque<Duration>,
@@ -112,120 +307,352 @@ impl App {
 	/// Creates a new app.
 	#[namefn]
 	pub fn new() -> Self {
-		let mut sys = System::new_all();
-
-		sys.refresh_all();
+		let config = config::current();
+		let history = config.history;
 
 		let mut app = App {
-			log: VecDeque::with_capacity(TRACKED_LOG_EVENTS),
-			theme: Theme::new(),
+			log: VecDeque::with_capacity(config.log_messages),
 			programs_scroll_state: ScrollbarState::default(),
+			process_table: TableState::default(),
+			selected_pid: None,
+			process_page: 1,
+			process_sort: config.process_sort,
+			process_tree: false,
+			collapsed: HashSet::new(),
+			search: None,
+			search_error: None,
+			search_before: None,
+			dialog: None,
+			input_target: None,
+			status: None,
 			tabs_index: 0,
-			tabs: Vec::from(TABS),
+			tabs: config.tabs_with_views(),
 			top_bar: TopBar::default(),
 			input: String::new(),
 			input_position: 0,
-			cpu_name: sys.cpus().first().unwrap().brand().to_owned(),
+			pending_keys: Vec::new(),
+			cpu_name: String::new(),
 			cpu_frequency: 0,
-			cpu_usage: VecDeque::with_capacity(CPU_USAGE_DATAPOINTS),
-			network_in: VecDeque::with_capacity(NETWORK_DATAPOINTS),
-			network_out: VecDeque::with_capacity(NETWORK_DATAPOINTS),
+			cpu_usage: VecDeque::with_capacity(history.cpu_usage),
+			cpu_cores: Vec::new(),
+			cpu_frequencies: Vec::new(),
+			cpu_scaling: Vec::new(),
+			cpu_times: VecDeque::with_capacity(history.cpu_times),
+			core_times: Vec::new(),
+			context_switches: 0.0,
+			interrupts: 0.0,
+			cpu_view: config.cpu_view,
+			memory: VecDeque::with_capacity(history.memory),
+			disk_read: VecDeque::with_capacity(history.disk),
+			disk_written: VecDeque::with_capacity(history.disk),
+			devices: Vec::new(),
+			filesystems: Vec::new(),
+			network_in: VecDeque::with_capacity(history.network),
+			network_out: VecDeque::with_capacity(history.network),
+			interfaces: Vec::new(),
+			system: None,
+			load: VecDeque::with_capacity(history.load),
+			sensors: Vec::new(),
+			pressure: VecDeque::with_capacity(history.pressure),
+			tracked_pressure: None,
 			processes: Vec::new(),
 			tracked_pid: None,
+			tracked_name: None,
 			tracked: None,
-			last_snapshot: Instant::now(),
 			working_prior: Duration::from_micros(0),
-			working_tick: VecDeque::with_capacity(DEBUG_TICK_DATAPOINTS),
-			real_tick: VecDeque::with_capacity(DEBUG_TICK_DATAPOINTS),
-			refresh_prior: Duration::from_micros(0),
-			refresh_tick: VecDeque::with_capacity(DEBUG_TICK_DATAPOINTS),
-			drawing_tick: VecDeque::with_capacity(DEBUG_TICK_DATAPOINTS),
-			event_tick: VecDeque::with_capacity(DEBUG_TICK_DATAPOINTS),
-			sys,
+			working_tick: VecDeque::with_capacity(history.debug_ticks),
+			real_tick: VecDeque::with_capacity(history.debug_ticks),
+			refresh_tick: VecDeque::with_capacity(history.debug_ticks),
+			drawing_tick: VecDeque::with_capacity(history.debug_ticks),
+			event_tick: VecDeque::with_capacity(history.debug_ticks),
+			config,
 		};
 
 		app.log("New app", NAME);
 
-		app.refresh();
+		if let Some(path) = &config::current().path {
+			app.log(format!("Loaded config from {}", path.display()), NAME);
+		}
 
 		app
 	}
 
-	/// Refreshes the data.
+	/// Adds the data of `sample`.
 	#[namefn]
-	pub fn refresh(&mut self) {
-		self.log("Refreshed app", NAME);
+	pub fn apply(&mut self, sample: Sample) {
+		self.log("Applied sample", NAME);
 
-		let tick = Instant::now();
+		let history = self.config.history;
 
-		self.sys.refresh_cpu();
-		let elapsed = self.last_snapshot.elapsed();
-		self.sys.refresh_networks();
-		self.last_snapshot = Instant::now();
-		self.sys.refresh_processes();
+		if let Some(cpu) = sample.cpu {
+			self.cpu_name = cpu.name;
+			self.cpu_frequency = cpu.frequency;
+			if self.cpu_usage.len() == history.cpu_usage {
+				self.cpu_usage.pop_front();
+			}
+			self.cpu_usage.push_back(cpu.usage);
 
-		let cpus = self.sys.cpus();
-		self.cpu_frequency = compute_frequency(cpus);
-		if self.cpu_usage.len() == CPU_USAGE_DATAPOINTS {
-			self.cpu_usage.pop_front();
-		}
-		self.cpu_usage.push_back(compute_usage(cpus));
+			self.cpu_cores.resize_with(cpu.cores.len(), || {
+				VecDeque::with_capacity(history.cpu_usage)
+			});
+			for (core, usage) in self.cpu_cores.iter_mut().zip(cpu.cores) {
+				if core.len() == history.cpu_usage {
+					core.pop_front();
+				}
+				core.push_back(usage);
+			}
+
+			self.cpu_frequencies.resize_with(cpu.frequencies.len(), || {
+				VecDeque::with_capacity(history.cpu_frequency)
+			});
+			for (core, frequency) in self.cpu_frequencies.iter_mut().zip(cpu.frequencies) {
+				if core.len() == history.cpu_frequency {
+					core.pop_front();
+				}
+				core.push_back(frequency);
+			}
 
-		let networks = self.sys.networks();
-		let network_in_last_sec = compute_in(networks);
-		if self.network_in.len() == NETWORK_DATAPOINTS {
-			self.network_in.pop_front();
+			if let Some(scaling) = cpu.scaling {
+				self.cpu_scaling = scaling;
+			}
+
+			if let Some(stat) = cpu.stat {
+				if self.cpu_times.len() == history.cpu_times {
+					self.cpu_times.pop_front();
+				}
+				self.cpu_times.push_back(stat.total);
+				self.core_times = stat.cores;
+				self.context_switches = stat.context_switches;
+				self.interrupts = stat.interrupts;
+			}
 		}
-		self.network_in
-			.push_back(per_second(network_in_last_sec, elapsed));
 
-		let network_out_last_sec = compute_out(networks);
-		if self.network_out.len() == NETWORK_DATAPOINTS {
-			self.network_out.pop_front();
+		if let Some(memory) = sample.memory {
+			if self.memory.len() == history.memory {
+				self.memory.pop_front();
+			}
+			self.memory.push_back(memory);
 		}
 
-		self.network_out
-			.push_back(per_second(network_out_last_sec, elapsed));
+		if let Some(disk) = sample.disk {
+			if self.disk_read.len() == history.disk {
+				self.disk_read.pop_front();
+			}
+			self.disk_read.push_back(
+				disk.devices
+					.iter()
+					.fold(Byte::new(0), |total, device| total + device.read),
+			);
+
+			if self.disk_written.len() == history.disk {
+				self.disk_written.pop_front();
+			}
+			self.disk_written.push_back(
+				disk.devices
+					.iter()
+					.fold(Byte::new(0), |total, device| total + device.written),
+			);
 
-		let processes = self.sys.processes();
-		self.processes = Vec::with_capacity(processes.len());
-		for (pid, process) in processes {
-			self.processes.push(Process::from_pp(pid, process))
+			self.devices = disk.devices;
+			if let Some(filesystems) = disk.filesystems {
+				self.filesystems = filesystems;
+			}
 		}
 
-		self.processes
-			.sort_by(|a, b| a.name.partial_cmp(&b.name).unwrap());
-
-		if let Some(pid) = self.tracked_pid {
-			if let Some(tracked_process) = &mut self.tracked {
-				if let Some(process) = processes.get(&pid) {
-					tracked_process.refresh(process, elapsed);
-				} else {
-					self.tracked_pid = None;
+		if let Some(network) = sample.network {
+			if self.network_in.len() == history.network {
+				self.network_in.pop_front();
+			}
+			self.network_in.push_back(network.network_in);
+
+			if self.network_out.len() == history.network {
+				self.network_out.pop_front();
+			}
+			self.network_out.push_back(network.network_out);
+
+			// Interfaces that went away are dropped along with their history.
+			let mut previous = std::mem::take(&mut self.interfaces);
+			for data in network.interfaces {
+				let mut interface = match previous.iter().position(|i| i.name == data.name) {
+					Some(index) => previous.swap_remove(index),
+					None => Interface {
+						name: data.name.clone(),
+						samples: VecDeque::with_capacity(history.network),
+					},
+				};
+				if interface.samples.len() == history.network {
+					interface.samples.pop_front();
 				}
-			} else if let Some(process) = processes.get(&pid) {
-				self.tracked = Some(TrackedProcess::new(&pid, process));
-				if let Some(tracked_process) = &mut self.tracked {
-					tracked_process.refresh(process, elapsed);
+				interface.samples.push_back(data);
+				self.interfaces.push(interface);
+			}
+		}
+
+		if let Some(system) = sample.system {
+			if self.load.len() == history.load {
+				self.load.pop_front();
+			}
+			self.load.push_back(system.load.clone());
+			self.system = Some(system);
+		}
+
+		if let Some(sensors) = sample.sensors {
+			let mut previous = std::mem::take(&mut self.sensors);
+			for data in sensors {
+				let mut sensor = match previous.iter().position(|s| s.label == data.label) {
+					Some(index) => previous.swap_remove(index),
+					None => Sensor {
+						label: data.label.clone(),
+						max: f32::NAN,
+						samples: VecDeque::with_capacity(history.sensors),
+					},
+				};
+				sensor.max = sensor.max.max(data.max).max(data.temperature);
+				if sensor.samples.len() == history.sensors {
+					sensor.samples.pop_front();
 				}
-			} else {
-				self.tracked_pid = None;
+				sensor.samples.push_back(data);
+				self.sensors.push(sensor);
 			}
 		}
 
-		self.tabs_index %= self.tabs.len();
+		if let Some(pressure) = sample.pressure {
+			if self.pressure.len() == history.pressure {
+				self.pressure.pop_front();
+			}
+			self.pressure.push_back(pressure.system);
+			self.tracked_pressure = pressure.tracked;
+		}
+
+		if let Some(processes) = sample.processes {
+			self.processes = processes;
+			self.sort_processes();
 
-		if self.refresh_tick.len() == DEBUG_TICK_DATAPOINTS {
+			let processes = &self.processes;
+			self.collapsed
+				.retain(|pid| processes.iter().any(|process| process.pid == *pid));
+		}
+
+		if let (None, Some(pattern)) = (self.tracked_pid, &self.tracked_name) {
+			let found = self
+				.processes
+				.iter()
+				.filter(|process| process.name.contains(pattern.as_str()))
+				.map(|process| process.pid)
+				.min();
+
+			if let Some(pid) = found {
+				self.tracked_pid = Some(Pid::from_u32(pid));
+				self.tracked_name = None;
+			}
+		}
+
+		match sample.tracked {
+			// Samples taken before the tracked process changed are ignored.
+			Some(TrackedSample { pid, process }) if Some(pid) == self.tracked_pid => {
+				match process {
+					Some(data) => self
+						.tracked
+						.get_or_insert_with(|| TrackedProcess::new(&data))
+						.refresh(&data, history.tracked_process),
+					None => self.tracked_pid = None,
+				}
+			},
+			_ => (),
+		}
+
+		if self.refresh_tick.len() == history.debug_ticks {
 			self.refresh_tick.pop_front();
 		}
-		self.refresh_tick.push_back(self.refresh_prior);
-		self.refresh_prior = tick.elapsed();
+		self.refresh_tick.push_back(sample.duration);
+	}
+
+	/// Gets the pid of the tracked process.
+	pub fn tracked_pid(&self) -> Option<Pid> {
+		self.tracked_pid
+	}
+
+	/// Switches to the active configuration, keeping all collected data and the tracked process.
+	///
+	/// Data exceeding the new history lengths is dropped, oldest first.
+	#[namefn]
+	pub fn reload_config(&mut self) {
+		let config = config::current();
+		let history = config.history;
+
+		let debug = self.has_debug();
+		self.tabs = config.tabs_with_views();
+		if debug {
+			self.enable_debug();
+		}
+		self.tabs_index %= self.tabs.len();
+		self.pending_keys.clear();
+
+		truncate_front(&mut self.log, config.log_messages);
+		truncate_front(&mut self.cpu_usage, history.cpu_usage);
+		for core in &mut self.cpu_cores {
+			truncate_front(core, history.cpu_usage);
+		}
+		for core in &mut self.cpu_frequencies {
+			truncate_front(core, history.cpu_frequency);
+		}
+		truncate_front(&mut self.cpu_times, history.cpu_times);
+		truncate_front(&mut self.memory, history.memory);
+		truncate_front(&mut self.disk_read, history.disk);
+		truncate_front(&mut self.disk_written, history.disk);
+		truncate_front(&mut self.network_in, history.network);
+		truncate_front(&mut self.network_out, history.network);
+		for interface in &mut self.interfaces {
+			truncate_front(&mut interface.samples, history.network);
+		}
+		truncate_front(&mut self.load, history.load);
+		for sensor in &mut self.sensors {
+			truncate_front(&mut sensor.samples, history.sensors);
+		}
+		truncate_front(&mut self.pressure, history.pressure);
+		for ticks in [
+			&mut self.working_tick,
+			&mut self.real_tick,
+			&mut self.refresh_tick,
+			&mut self.drawing_tick,
+			&mut self.event_tick,
+		] {
+			truncate_front(ticks, history.debug_ticks);
+		}
+
+		if let Some(tracked) = &mut self.tracked {
+			truncate_front(&mut tracked.cpu_usage, history.tracked_process);
+			truncate_front(&mut tracked.memory, history.tracked_process);
+			truncate_front(&mut tracked.written, history.tracked_process);
+			truncate_front(&mut tracked.read, history.tracked_process);
+		}
+
+		// Only a changed setting overrides the view the user toggled to.
+		if config.cpu_view != self.config.cpu_view {
+			self.cpu_view = config.cpu_view;
+		}
+		if config.process_sort != self.config.process_sort {
+			self.process_sort = config.process_sort;
+			self.sort_processes();
+		}
+
+		self.config = config;
+
+		match &self.config.path {
+			Some(path) => self.log(format!("Reloaded config from {}", path.display()), NAME),
+			None => self.log("Reloaded config", NAME),
+		}
 	}
 
 	/// Gets the current tab.
-	pub fn current_tab(&self) -> Screen {
+	pub fn current_tab(&self) -> &Tab {
 		let index = self.tabs_index % self.tabs.len();
-		self.tabs[index]
+		&self.tabs[index]
+	}
+
+	/// Gets all tabs in the order they are displayed.
+	pub fn tabs(&self) -> &[Tab] {
+		&self.tabs
 	}
 
 	/// Increments the tab index by 1.
@@ -249,22 +676,80 @@ impl App {
 		self.tabs_index % self.tabs.len()
 	}
 
+	/// Opens the input asking for `prompt`.
+	///
+	/// The search input starts with the current search, a view can only be saved while searching.
+	pub fn start_input(&mut self, prompt: Prompt) {
+		match prompt {
+			Prompt::Track => (),
+			Prompt::Search => {
+				self.input = self
+					.search
+					.as_ref()
+					.map_or_else(String::new, Filter::to_string);
+				self.input_position = self.input.len();
+				self.search_before = self.search.clone();
+			},
+			Prompt::View if self.search.is_none() => {
+				self.set_status(Status::error("Search for processes before saving a view"));
+				return;
+			},
+			Prompt::View => (),
+			Prompt::Nice | Prompt::IoPriority | Prompt::Affinity => {
+				let Some(target) = self.selected_target() else {
+					return;
+				};
+				// The current value is a starting point, it is fine to start empty without it.
+				let current = match prompt {
+					Prompt::Nice => control::read_nice(target.pid).map(|nice| nice.to_string()),
+					Prompt::IoPriority => {
+						control::read_io_priority(target.pid).map(|priority| priority.to_string())
+					},
+					_ => control::read_affinity(target.pid).map(|affinity| affinity.to_string()),
+				};
+				self.input = current.unwrap_or_default();
+				self.input_position = self.input.len();
+				self.input_target = Some(target);
+			},
+		}
+
+		self.top_bar = TopBar::Input(prompt);
+	}
+
 	/// Inputs the given char.
 	pub fn input_type(&mut self, ch: char) {
 		self.input.insert(self.input_position, ch);
-		self.input_position += 1;
+		self.input_position += ch.len_utf8();
+		self.input_changed();
 	}
 
 	/// Deletes the char prior to the cursor.
 	pub fn input_backspace(&mut self) {
-		if self.input_position > 0 {
-			self.input_position -= 1;
+		if let Some(ch) = self.input[..self.input_position].chars().next_back() {
+			self.input_position -= ch.len_utf8();
+			self.input.remove(self.input_position);
+			self.input_changed();
+		}
+	}
 
-			let before_char_to_delete = self.input.chars().take(self.input_position);
+	/// Applies the typed search as soon as it can be parsed.
+	fn input_changed(&mut self) {
+		if self.top_bar != TopBar::Input(Prompt::Search) {
+			return;
+		}
 
-			let after_char_to_delete = self.input.chars().skip(self.input_position + 1);
+		if self.input.trim().is_empty() {
+			self.search = None;
+			self.search_error = None;
+			return;
+		}
 
-			self.input = before_char_to_delete.chain(after_char_to_delete).collect();
+		match self.input.parse() {
+			Ok(filter) => {
+				self.search = Some(filter);
+				self.search_error = None;
+			},
+			Err(e) => self.search_error = Some(e),
 		}
 	}
 
@@ -273,13 +758,13 @@ impl App {
 	pub fn arrow_event(&mut self, arrow: KeyCode) {
 		match arrow {
 			Left => {
-				if self.input_position > 0 {
-					self.input_position -= 1;
+				if let Some(ch) = self.input[..self.input_position].chars().next_back() {
+					self.input_position -= ch.len_utf8();
 				}
 			},
 			Right => {
-				if self.input_position > self.input.len() {
-					self.input_position += 1;
+				if let Some(ch) = self.input[self.input_position..].chars().next() {
+					self.input_position += ch.len_utf8();
 				}
 			},
 			Up => self.input_position = 0,
@@ -293,52 +778,441 @@ impl App {
 		self.top_bar = TopBar::Tabs;
 		self.input.clear();
 		self.input_position = 0;
+		self.search_error = None;
+		self.input_target = None;
+	}
+
+	/// Closes the input without entering it, restoring the search if it was edited.
+	pub fn cancel_input(&mut self) {
+		if self.top_bar == TopBar::Input(Prompt::Search) {
+			self.search = self.search_before.take();
+		}
+
+		self.wipe_input();
 	}
 
-	/// Enters the input as tracked pid.
+	/// Enters the input.
 	pub fn input_enter(&mut self) {
-		let pid = match Pid::from_str(&self.input) {
-			Ok(pid) => pid,
+		match self.top_bar {
+			TopBar::Input(Prompt::Track) => {
+				let pid = match Pid::from_str(&self.input) {
+					Ok(pid) => pid,
+					Err(e) => {
+						error!("{}", e);
+						return;
+					},
+				};
+
+				self.track(pid);
+			},
+			TopBar::Input(Prompt::Search) => {
+				if let Some(e) = &self.search_error {
+					self.set_status(Status::error(format!("Invalid search: {}", e)));
+					return;
+				}
+				self.search_before = None;
+			},
+			TopBar::Input(Prompt::View) => {
+				let name = self.input.trim().to_owned();
+				if name.is_empty() {
+					self.set_status(Status::error("A view needs a name"));
+					return;
+				}
+				self.save_view(name);
+			},
+			TopBar::Input(prompt @ (Prompt::Nice | Prompt::IoPriority | Prompt::Affinity)) => {
+				let Some(target) = self.input_target.clone() else {
+					return;
+				};
+				let input = self.input.trim();
+				let operation = match prompt {
+					Prompt::Nice => match input.parse() {
+						Ok(nice @ -20..=19) => Ok(Operation::Nice(nice)),
+						_ => Err(format!(
+							"invalid nice value `{}`, expected -20 to 19",
+							input
+						)),
+					},
+					Prompt::IoPriority => input.parse().map(Operation::IoPriority),
+					_ => input.parse().map(Operation::Affinity),
+				};
+
+				match operation {
+					Ok(operation) => self.dialog = Some(Dialog::Confirm { target, operation }),
+					Err(e) => {
+						self.set_status(Status::error(e));
+						return;
+					},
+				}
+			},
+			TopBar::Tabs => (),
+		}
+
+		self.wipe_input();
+	}
+
+	/// Gets the process selected in the process table, setting an error status if there is none.
+	fn selected_target(&mut self) -> Option<Target> {
+		let target = self.selected_pid.and_then(|pid| {
+			let process = self.processes.iter().find(|process| process.pid == pid)?;
+			Some(Target {
+				pid,
+				name: process.name.clone(),
+			})
+		});
+
+		if target.is_none() {
+			self.set_status(Status::error("Select a process in the process table first"));
+		}
+		target
+	}
+
+	/// Opens the dialog picking the signal sent to the selected process.
+	pub fn open_signals(&mut self) {
+		if let Some(target) = self.selected_target() {
+			self.dialog = Some(Dialog::Signals {
+				target,
+				selected: 0,
+			});
+		}
+	}
+
+	/// Moves the selection of the signal dialog down by `rows`, or up if negative.
+	pub fn move_dialog_selection(&mut self, rows: isize) {
+		if let Some(Dialog::Signals { selected, .. }) = &mut self.dialog {
+			*selected = selected
+				.saturating_add_signed(rows)
+				.min(Signal::ALL.len() - 1);
+		}
+	}
+
+	/// Confirms the open dialog, either picking the signal or applying the change.
+	///
+	/// The result of the change is displayed in the status line and logged.
+	#[namefn]
+	pub fn confirm_dialog(&mut self) {
+		match self.dialog.take() {
+			Some(Dialog::Signals { target, selected }) => {
+				self.dialog = Some(Dialog::Confirm {
+					target,
+					operation: Operation::Signal(Signal::ALL[selected]),
+				});
+			},
+			Some(Dialog::Confirm { target, operation }) => {
+				let name = target.to_string();
+				let description = operation.describe(&name, false);
+				let status = match operation.apply(target.pid) {
+					Ok(()) => Status::info(capitalize(&operation.describe(&name, true))),
+					Err(e) if e.kind() == ErrorKind::PermissionDenied => Status::error(format!(
+						"Can't {}: {}, tusk needs more privileges",
+						description, e
+					)),
+					Err(e) => Status::error(format!("Can't {}: {}", description, e)),
+				};
+				self.log(status.message.as_str(), NAME);
+				self.set_status(status);
+			},
+			None => (),
+		}
+	}
+
+	/// Closes the open dialog without changing anything.
+	pub fn cancel_dialog(&mut self) {
+		self.dialog = None;
+	}
+
+	/// Saves the filter of the current tab and the search as a view named `name`,
+	/// then switches to its tab.
+	#[namefn]
+	fn save_view(&mut self, name: String) {
+		let Some(search) = self.search.clone() else {
+			return;
+		};
+		let filter = match &self.current_tab().filter {
+			Some(filter) => filter.and(&search),
+			None => search,
+		};
+		let view = View { name, filter };
+
+		match config::save_view(&self.config, view.clone()) {
+			Ok(path) => {
+				let message = format!("Saved view `{}` to {}", view.name, path.display());
+				self.log(message.as_str(), NAME);
+				self.set_status(Status::info(message));
+			},
 			Err(e) => {
-				error!("{}", e);
+				self.set_status(Status::error(format!("Can't save the view: {}", e)));
 				return;
 			},
+		}
+
+		let tab = Tab {
+			title: Some(view.name),
+			filter: Some(view.filter),
+			..Tab::new(Screen::Processes)
 		};
+		let existing = self
+			.tabs
+			.iter()
+			.position(|other| other.screen == Screen::Processes && other.title == tab.title);
+		self.tabs_index = match existing {
+			Some(index) => {
+				self.tabs[index] = tab;
+				index
+			},
+			None => {
+				self.tabs.push(tab);
+				self.tabs.len() - 1
+			},
+		};
+		self.search = None;
+	}
 
+	/// Starts tracking the process with the given pid.
+	pub fn track(&mut self, pid: Pid) {
 		self.tracked_pid = Some(pid);
+		self.tracked_name = None;
+		self.tracked = None;
+	}
 
-		self.wipe_input();
+	/// Starts tracking the first process whose name contains `pattern`.
+	///
+	/// If no such process exists yet, it is tracked as soon as it appears.
+	#[namefn]
+	pub fn track_name(&mut self, pattern: &str) {
+		self.tracked_pid = None;
+		self.tracked_name = Some(pattern.to_owned());
+		self.tracked = None;
+		self.log(format!("Tracking processes matching `{}`", pattern), NAME);
+	}
+
+	/// Switches to the first tab of the given screen.
+	/// The debug tab is shown if it isn't shown already.
+	pub fn select_tab(&mut self, screen: Screen) {
+		if screen == Screen::Debug {
+			self.enable_debug();
+		}
+
+		if let Some(index) = self.tabs.iter().position(|tab| tab.screen == screen) {
+			self.tabs_index = index;
+		}
+	}
+
+	/// Displays the given status in the status line.
+	pub fn set_status(&mut self, status: Status) {
+		self.status = Some(status);
+	}
+
+	/// Removes the current status from the status line.
+	pub fn clear_status(&mut self) {
+		self.status = None;
 	}
 
 	/// Makes the debug panel visible.
 	pub fn enable_debug(&mut self) {
-		if !self.tabs.contains(&Screen::Debug) {
-			self.tabs.push(Screen::Debug)
+		if !self.has_debug() {
+			self.tabs.push(Tab::new(Screen::Debug))
 		}
 	}
 
 	/// Makes the debug panel invisible.
+	/// Does nothing if only debug tabs are configured.
 	pub fn disable_debug(&mut self) {
-		self.tabs = self
-			.tabs
-			.iter()
-			.filter(|&&a| a != Screen::Debug)
-			.map(|a| a.to_owned())
-			.collect();
+		if self.tabs.iter().all(|tab| tab.screen == Screen::Debug) {
+			return;
+		}
+
+		self.tabs.retain(|tab| tab.screen != Screen::Debug);
+		self.tabs_index %= self.tabs.len();
+	}
+
+	/// Whether a debug tab is shown.
+	fn has_debug(&self) -> bool {
+		self.tabs.iter().any(|tab| tab.screen == Screen::Debug)
 	}
 
 	/// Switches the debug panel on or off depending on prior state.
 	pub fn switch_debug(&mut self) {
-		if self.tabs.contains(&Screen::Debug) {
+		if self.has_debug() {
 			self.disable_debug()
 		} else {
 			self.enable_debug()
 		}
 	}
 
+	/// Gets the processes listed in the process table of the current tab, in their order.
+	pub fn visible_processes(&self) -> Vec<&Process> {
+		if self.process_tree {
+			self.tree_rows()
+				.into_iter()
+				.map(|row| row.process)
+				.collect()
+		} else {
+			self.filtered_processes()
+		}
+	}
+
+	/// Gets the processes of the current tab nested under their parents.
+	pub fn tree_rows(&self) -> Vec<TreeRow<'_>> {
+		process_tree::build(&self.filtered_processes(), &self.collapsed)
+	}
+
+	/// Gets the processes passing the filter of the current tab and the search, in sort order.
+	fn filtered_processes(&self) -> Vec<&Process> {
+		let filter = self.current_tab().filter.as_ref();
+		self.processes
+			.iter()
+			.filter(|process| filter.is_none_or(|filter| filter.matches(process)))
+			.filter(|process| {
+				self.search
+					.as_ref()
+					.is_none_or(|search| search.matches(process))
+			})
+			.collect()
+	}
+
+	/// Gets the index of the selected process in `processes`.
+	///
+	/// If the selected process is gone, the process now in its row is selected instead.
+	pub fn selected_index(&self, processes: &[&Process]) -> Option<usize> {
+		if processes.is_empty() {
+			return None;
+		}
+
+		self.selected_pid
+			.and_then(|pid| processes.iter().position(|process| process.pid == pid))
+			.or_else(|| {
+				let row = self.process_table.selected().unwrap_or_default();
+				Some(row.min(processes.len() - 1))
+			})
+	}
+
+	/// Selects the process with `pid`, listed in row `index` of the process table.
+	pub fn select_process(&mut self, pid: Option<u32>, index: Option<usize>) {
+		self.selected_pid = pid;
+		self.process_table.select(index);
+	}
+
+	/// Moves the process selection down by `rows`, or up if negative, stopping at either end.
+	#[namefn]
+	pub fn move_selection(&mut self, rows: isize) {
+		let processes = self.visible_processes();
+		let Some(index) = self.selected_index(&processes) else {
+			return;
+		};
+
+		let index = index.saturating_add_signed(rows).min(processes.len() - 1);
+		let pid = processes[index].pid;
+		self.select_process(Some(pid), Some(index));
+		self.log(format!("Selected process {}", pid), NAME);
+	}
+
+	/// Switches between listing the processes flat and nesting them under their parents.
+	#[namefn]
+	pub fn toggle_process_tree(&mut self) {
+		self.process_tree = !self.process_tree;
+		let view = if self.process_tree { "tree" } else { "list" };
+		self.log(format!("Showing processes as {}", view), NAME);
+	}
+
+	/// Hides the children of the selected process in the tree, or shows them again.
+	#[namefn]
+	pub fn toggle_collapsed(&mut self) {
+		let Some(pid) = self.selected_pid.filter(|_| self.process_tree) else {
+			return;
+		};
+
+		if !self.collapsed.remove(&pid) {
+			self.collapsed.insert(pid);
+			self.log(format!("Collapsed process {}", pid), NAME);
+		} else {
+			self.log(format!("Expanded process {}", pid), NAME);
+		}
+	}
+
+	/// Selects the parent of the selected process, if it is listed.
+	#[namefn]
+	pub fn select_parent(&mut self) {
+		let processes = self.visible_processes();
+		let Some(index) = self.selected_index(&processes) else {
+			return;
+		};
+		let process = processes[index];
+		let Some(parent) = process.parent else {
+			let message = format!("{} ({}) has no parent", process.name, process.pid);
+			self.set_status(Status::error(message));
+			return;
+		};
+
+		match processes.iter().position(|process| process.pid == parent) {
+			Some(index) => {
+				self.select_process(Some(parent), Some(index));
+				self.log(format!("Selected process {}", parent), NAME);
+			},
+			None => {
+				let message = format!(
+					"The parent {} of {} ({}) isn't listed",
+					parent, process.name, process.pid
+				);
+				self.set_status(Status::error(message));
+			},
+		}
+	}
+
+	/// Sorts the processes by the column and order of `process_sort`.
+	///
+	/// The sort is stable, so processes with equal values stay sorted by name as sampled.
+	fn sort_processes(&mut self) {
+		let ProcessSort { column, descending } = self.process_sort;
+		self.processes.sort_by(|a, b| {
+			let ordering = match column {
+				SortColumn::Pid => a.pid.cmp(&b.pid),
+				SortColumn::Name => a.name.cmp(&b.name),
+				SortColumn::Memory => a.memory.cmp(&b.memory),
+				SortColumn::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+				SortColumn::Time => a.time.cmp(&b.time),
+				SortColumn::Written => a.total_written.cmp(&b.total_written),
+				SortColumn::Read => a.total_read.cmp(&b.total_read),
+			};
+			if descending {
+				ordering.reverse()
+			} else {
+				ordering
+			}
+		});
+	}
+
+	/// Sorts the processes by `column`, keeping the order.
+	#[namefn]
+	pub fn sort_by(&mut self, column: SortColumn) {
+		self.process_sort.column = column;
+		self.sort_processes();
+		self.log(format!("Sorting processes by {:?}", column), NAME);
+	}
+
+	/// Switches between sorting the processes ascending and descending.
+	#[namefn]
+	pub fn toggle_sort_order(&mut self) {
+		self.process_sort.descending = !self.process_sort.descending;
+		self.sort_processes();
+		let order = if self.process_sort.descending {
+			"descending"
+		} else {
+			"ascending"
+		};
+		self.log(format!("Sorting processes {}", order), NAME);
+	}
+
+	/// Switches to the next way of showing the cpu usage.
+	#[namefn]
+	pub fn toggle_cpu_view(&mut self) {
+		self.cpu_view = self.cpu_view.next();
+		self.log(format!("Cpu view {:?}", self.cpu_view), NAME);
+	}
+
 	/// Add the working tick time to app.
 	pub fn working_tick(&mut self, tick: Duration) {
-		if self.working_tick.len() == DEBUG_TICK_DATAPOINTS {
+		if self.working_tick.len() == self.config.history.debug_ticks {
 			self.working_tick.pop_front();
 		}
 		self.working_tick.push_back(self.working_prior);
@@ -347,7 +1221,7 @@ impl App {
 
 	/// Add the real tick time to app.
 	pub fn real_tick(&mut self, tick: Duration) {
-		if self.real_tick.len() == DEBUG_TICK_DATAPOINTS {
+		if self.real_tick.len() == self.config.history.debug_ticks {
 			self.real_tick.pop_front();
 		}
 		self.real_tick.push_back(tick);
@@ -355,7 +1229,7 @@ impl App {
 
 	/// Add the drawing tick time to app.
 	pub fn draw_tick(&mut self, tick: Duration) {
-		if self.drawing_tick.len() == DEBUG_TICK_DATAPOINTS {
+		if self.drawing_tick.len() == self.config.history.debug_ticks {
 			self.drawing_tick.pop_front();
 		}
 		self.drawing_tick.push_back(tick);
@@ -363,7 +1237,7 @@ impl App {
 
 	/// Add the event tick time to app.
 	pub fn event_tick(&mut self, tick: Duration) {
-		if self.event_tick.len() == DEBUG_TICK_DATAPOINTS {
+		if self.event_tick.len() == self.config.history.debug_ticks {
 			self.event_tick.pop_front();
 		}
 		self.event_tick.push_back(tick);
@@ -374,13 +1248,29 @@ impl App {
 		T: Into<String>,
 		U: Into<String>,
 	{
-		if self.log.len() == LOG_MESSAGES {
+		if self.log.len() == self.config.log_messages {
 			self.log.pop_front();
 		}
 		self.log.push_back(Log::new(message.into(), caller.into()));
 	}
 }
 
+/// The history of a network interface.
+#[derive(Debug)]
+pub struct Interface {
+	pub name: String,
+	pub samples: VecDeque<InterfaceSample>,
+}
+
+/// The history of a temperature sensor.
+#[derive(Debug)]
+pub struct Sensor {
+	pub label: String,
+	/// The highest temperature seen since tusk started, `NaN` if none could be read.
+	pub max: f32,
+	pub samples: VecDeque<SensorSample>,
+}
+
 /// A tracked process.
 #[derive(Debug)]
 pub struct TrackedProcess {
@@ -397,61 +1287,63 @@ pub struct TrackedProcess {
 }
 
 impl TrackedProcess {
-	/// Create a new tracked process from pid and process.
-	pub fn new(pid: &Pid, process: &Proc) -> Self {
-		let disk_usage = process.disk_usage();
+	/// Create a new tracked process without any datapoints.
+	pub fn new(data: &TrackedData) -> Self {
+		let process = &data.process;
 
 		TrackedProcess {
-			pid: pid.as_u32(),
-			name: process.name().to_owned(),
-			time: process.run_time(),
-			status: process.status(),
-			total_written: MegaByte::new(disk_usage.total_written_bytes),
-			total_read: MegaByte::new(disk_usage.total_read_bytes),
-			cpu_usage: VecDeque::from(vec![process.cpu_usage()]),
-			memory: VecDeque::from(vec![MegaByte::new(process.memory())]),
-			written: VecDeque::from(vec![KiloByte::new(disk_usage.written_bytes)]),
-			read: VecDeque::from(vec![KiloByte::new(disk_usage.read_bytes)]),
+			pid: process.pid,
+			name: process.name.clone(),
+			time: process.time,
+			status: process.status,
+			total_written: process.total_written,
+			total_read: process.total_read,
+			cpu_usage: VecDeque::new(),
+			memory: VecDeque::new(),
+			written: VecDeque::new(),
+			read: VecDeque::new(),
 		}
 	}
 
-	/// Refresh the tracked process.
-	pub fn refresh(&mut self, process: &Proc, elapsed: Duration) {
-		self.time = process.run_time();
-		self.status = process.status();
+	/// Refresh the tracked process, keeping `history` datapoints.
+	pub fn refresh(&mut self, data: &TrackedData, history: usize) {
+		let process = &data.process;
 
-		let disk_usage = process.disk_usage();
-		self.total_written = MegaByte::new(disk_usage.total_written_bytes);
-		self.total_read = MegaByte::new(disk_usage.total_read_bytes);
+		self.time = process.time;
+		self.status = process.status;
+		self.total_written = process.total_written;
+		self.total_read = process.total_read;
 
-		if self.memory.len() == TRACKED_PROCESS_DATAPOINTS {
+		if self.memory.len() == history {
 			self.memory.pop_front();
 		}
-		self.memory.push_back(MegaByte::new(process.memory()));
+		self.memory.push_back(process.memory);
 
-		if self.cpu_usage.len() == TRACKED_PROCESS_DATAPOINTS {
+		if self.cpu_usage.len() == history {
 			self.cpu_usage.pop_front();
 		}
-		self.cpu_usage.push_back(process.cpu_usage());
+		self.cpu_usage.push_back(process.cpu_usage);
 
-		if self.written.len() == TRACKED_PROCESS_DATAPOINTS {
+		if self.written.len() == history {
 			self.written.pop_front();
 		}
-		self.written
-			.push_back(per_second(MegaByte::new(disk_usage.written_bytes), elapsed).as_kilo_byte());
+		self.written.push_back(data.written);
 
-		if self.read.len() == TRACKED_PROCESS_DATAPOINTS {
+		if self.read.len() == history {
 			self.read.pop_front();
 		}
-		self.read
-			.push_back(per_second(MegaByte::new(disk_usage.written_bytes), elapsed).as_kilo_byte());
+		self.read.push_back(data.read);
 	}
 }
 
 /// A generic process.
 pub struct Process {
 	pub pid: u32,
+	/// The pid of the process that started this one, if any.
+	pub parent: Option<u32>,
 	pub name: String,
+	/// The name of the user running the process, or its user id if the name is unknown.
+	pub user: String,
 	pub time: u64,
 	pub memory: MegaByte,
 	pub status: ProcessStatus,
@@ -461,10 +1353,15 @@ pub struct Process {
 }
 
 impl Process {
-	/// Create the process from a pid and process.
-	pub fn from_pp(pid: &Pid, process: &Proc) -> Self {
+	/// Create the process from a pid and process, looking up its user in `sys`.
+	pub fn from_pp(pid: &Pid, process: &Proc, sys: &System) -> Self {
 		let pid = pid.as_u32();
+		let parent = process.parent().map(|parent| parent.as_u32());
 		let name = process.name().to_owned();
+		let user = process.user_id().map_or_else(String::new, |uid| {
+			sys.get_user_by_id(uid)
+				.map_or_else(|| uid.to_string(), |user| user.name().to_owned())
+		});
 		let memory = MegaByte::new(process.memory());
 		let time = process.run_time();
 		let status = process.status();
@@ -475,7 +1372,9 @@ impl Process {
 
 		Process {
 			pid,
+			parent,
 			name,
+			user,
 			memory,
 			time,
 			status,
@@ -496,3 +1395,38 @@ impl Log {
 		Log { message, caller }
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn input_with_multibyte_chars() {
+		let mut app = App::new();
+		app.start_input(Prompt::Track);
+
+		for ch in "äb€".chars() {
+			app.input_type(ch);
+		}
+		assert_eq!(app.input, "äb€");
+		assert_eq!(app.input_position, app.input.len());
+
+		app.arrow_event(Left);
+		app.arrow_event(Left);
+		app.input_type('ß');
+		assert_eq!(app.input, "äßb€");
+
+		app.input_backspace();
+		app.input_backspace();
+		assert_eq!(app.input, "b€");
+		assert_eq!(app.input_position, 0);
+
+		app.input_backspace();
+		app.arrow_event(Left);
+		app.arrow_event(Right);
+		app.arrow_event(Right);
+		app.arrow_event(Right);
+		app.input_type('!');
+		assert_eq!(app.input, "b€!");
+	}
+}
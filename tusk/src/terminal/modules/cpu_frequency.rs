@@ -0,0 +1,80 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+	config::theme,
+	terminal::{
+		app_util::{history_width, BARS},
+		App,
+	},
+};
+
+/// Draws a table with the current, lowest and highest frequency of every core, its scaling
+/// governor and limits and a small graph of its frequency history.
+///
+/// The history is scaled to the upper limit if known, so throttling shows as a drop.
+pub fn draw_frequency<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	let header_cells = [
+		"Core", "Current", "Min", "Max", "Governor", "Limits", "History",
+	]
+	.iter()
+	.map(|h| Cell::from(*h).style(theme.header));
+	let header = Row::new(header_cells).style(theme.graph_1).height(1);
+
+	let widths = [6, 9, 9, 9, 12, 11];
+	let history_width = history_width(area, &widths);
+
+	let rows = app
+		.cpu_frequencies
+		.iter()
+		.enumerate()
+		.map(|(core, history)| {
+			let scaling = app.cpu_scaling.get(core).cloned().unwrap_or_default();
+			let current = history.back().copied().unwrap_or_default();
+			let min = history.iter().copied().min().unwrap_or_default();
+			let max = history.iter().copied().max().unwrap_or_default();
+
+			let limits = match (scaling.min, scaling.max) {
+				(Some(min), Some(max)) => format!("{}-{}", min, max),
+				_ => String::from("-"),
+			};
+
+			let top = scaling.max.unwrap_or(max).max(1);
+			let graph: String = history
+				.iter()
+				.skip(history.len().saturating_sub(history_width as usize))
+				.map(|&frequency| {
+					let index = frequency.min(top) * (BARS.len() as u64 - 1) / top;
+					BARS[index as usize]
+				})
+				.collect();
+
+			Row::new([
+				Cell::from(format!("{}", core)),
+				Cell::from(format!("{}MHz", current)),
+				Cell::from(format!("{}MHz", min)),
+				Cell::from(format!("{}MHz", max)),
+				Cell::from(scaling.governor.unwrap_or_else(|| String::from("-"))),
+				Cell::from(limits),
+				Cell::from(graph).style(theme.graph_1),
+			])
+			.style(theme.text)
+		});
+
+	let constraints: Vec<Constraint> = widths
+		.iter()
+		.map(|&width| Constraint::Length(width))
+		.chain([Constraint::Length(history_width)])
+		.collect();
+	let table = Table::new(rows)
+		.header(header)
+		.block(
+			Block::default()
+				.borders(Borders::ALL)
+				.title("Cpu Frequency".bold())
+				.border_style(theme.window),
+		)
+		.widths(&constraints);
+
+	f.render_widget(table, area);
+}
@@ -1,15 +1,36 @@
-use std::sync::Arc;
+use std::collections::VecDeque;
 
 use ratatui::{
 	prelude::*,
-	widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
+	widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline, Wrap},
 	Frame,
 };
 
-use crate::{config::theme::THEME, datapoints::CPU_USAGE_DATAPOINTS, terminal::App};
+use super::stacked::{Layer, StackedGraph};
+use crate::{
+	config::{layout::CpuView, theme},
+	sampler::CpuTimes,
+	terminal::App,
+};
+
+/// The height of a core in the grid, one line for its name and one for its graph.
+const CORE_HEIGHT: u16 = 2;
+
+/// The characters of the heatmap, from idle to fully used.
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
 
+/// Draws the cpu usage the way `app.cpu_view` selects.
 pub fn draw_usage<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-	let theme = Arc::clone(&THEME);
+	match app.cpu_view {
+		CpuView::Aggregate => draw_aggregate(f, app, area),
+		CpuView::Cores => draw_cores(f, app, area),
+		CpuView::Heatmap => draw_heatmap(f, app, area),
+		CpuView::Times => draw_times(f, app, area),
+	}
+}
+
+fn draw_aggregate<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
 	let data: Vec<(f64, f64)> = app
 		.cpu_usage
 		.iter()
@@ -33,7 +54,7 @@ pub fn draw_usage<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
 		.x_axis(
 			Axis::default()
 				.style(theme.axis)
-				.bounds([0.0, CPU_USAGE_DATAPOINTS as f64]),
+				.bounds([0.0, app.config.history.cpu_usage as f64]),
 		)
 		.y_axis(
 			Axis::default()
@@ -50,3 +71,282 @@ pub fn draw_usage<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
 
 	f.render_widget(chart, area);
 }
+
+/// Draws a grid with the name, current usage and a sparkline of every core.
+fn draw_cores<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	let block = Block::default()
+		.title("Cpu Usage per Core".bold())
+		.borders(Borders::ALL)
+		.border_style(theme.window);
+	let inner = block.inner(area);
+	f.render_widget(block, area);
+
+	let cores = app.cpu_cores.len() as u16;
+	if cores == 0 || inner.height < CORE_HEIGHT {
+		return;
+	}
+
+	// Uses as few columns as possible while fitting all cores, but at least a square grid.
+	let fitting = cores.div_ceil(inner.height / CORE_HEIGHT);
+	let columns = fitting.max((cores as f64).sqrt().ceil() as u16).min(cores);
+	let rows = cores.div_ceil(columns);
+
+	let row_areas = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints(vec![Constraint::Ratio(1, rows as u32); rows as usize])
+		.split(inner);
+
+	for (row, row_area) in row_areas.iter().enumerate() {
+		let cells = Layout::default()
+			.direction(Direction::Horizontal)
+			.constraints(vec![Constraint::Ratio(1, columns as u32); columns as usize])
+			.split(*row_area);
+
+		for (column, cell) in cells.iter().enumerate() {
+			let core = row * columns as usize + column;
+			if let Some(history) = app.cpu_cores.get(core) {
+				draw_core(f, core, history, *cell);
+			}
+		}
+	}
+}
+
+fn draw_core<B: Backend>(f: &mut Frame<B>, core: usize, history: &VecDeque<f32>, area: Rect) {
+	let theme = theme::current();
+	let usage = history.back().copied().unwrap_or_default();
+
+	let title = Rect { height: 1, ..area };
+	f.render_widget(
+		Paragraph::new(format!("Cpu{} {:.0}%", core, usage)).style(theme.text),
+		title,
+	);
+
+	// Only the newest datapoints that fit are drawn, leaving a column of space to the next core.
+	let graph = Rect {
+		y: area.y + 1,
+		height: area.height - 1,
+		width: area.width.saturating_sub(1),
+		..area
+	};
+	let data: Vec<u64> = history
+		.iter()
+		.skip(history.len().saturating_sub(graph.width as usize))
+		.map(|&usage| usage.round() as u64)
+		.collect();
+
+	f.render_widget(
+		Sparkline::default()
+			.style(usage_style(usage))
+			.max(100)
+			.data(&data),
+		graph,
+	);
+}
+
+/// Draws a row for every core, with a shaded cell for each of its newest datapoints.
+///
+/// Cores that don't fit into `area` are left out.
+fn draw_heatmap<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	let block = Block::default()
+		.title("Cpu Usage Heatmap".bold())
+		.borders(Borders::ALL)
+		.border_style(theme.window);
+	let inner = block.inner(area);
+
+	let label_width = format!("Cpu{}", app.cpu_cores.len().saturating_sub(1)).len() + 1;
+	let cells = (inner.width as usize).saturating_sub(label_width);
+
+	let lines: Vec<Line> = app
+		.cpu_cores
+		.iter()
+		.enumerate()
+		.map(|(core, history)| {
+			let mut spans = vec![Span::styled(
+				format!("{:<width$}", format!("Cpu{}", core), width = label_width),
+				theme.text,
+			)];
+			spans.extend(
+				history
+					.iter()
+					.skip(history.len().saturating_sub(cells))
+					.map(|&usage| Span::styled(shade(usage).to_string(), usage_style(usage))),
+			);
+			Line::from(spans)
+		})
+		.collect();
+
+	f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Draws the share of time spent in every state as a stacked graph, with a stacked bar for every
+/// core below it if there is room.
+///
+/// Idle time is left empty, so the graph shows the usage split into its parts.
+fn draw_times<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	let block = Block::default()
+		.title("Cpu Time".bold())
+		.borders(Borders::ALL)
+		.border_style(theme.window);
+	let inner = block.inner(area);
+	f.render_widget(block, area);
+
+	let layers = [
+		Layer {
+			symbol: "█",
+			style: theme.graph_1,
+		},
+		Layer {
+			symbol: "▒",
+			style: theme.graph_1,
+		},
+		Layer {
+			symbol: "░",
+			style: theme.graph_1,
+		},
+		Layer {
+			symbol: "█",
+			style: theme.graph_3,
+		},
+		Layer {
+			symbol: "▓",
+			style: theme.graph_3,
+		},
+		Layer {
+			symbol: "▒",
+			style: theme.graph_3,
+		},
+		Layer {
+			symbol: "▒",
+			style: theme.graph_2,
+		},
+		Layer {
+			symbol: "█",
+			style: theme.graph_2,
+		},
+	];
+	let names = [
+		"User", "Nice", "Guest", "System", "Irq", "SoftIrq", "Steal", "IOWait",
+	];
+
+	let latest = app.cpu_times.back().copied().unwrap_or_default();
+	let mut legend: Vec<Span> = layers
+		.iter()
+		.zip(names)
+		.zip(stacked(&latest))
+		.flat_map(|((layer, name), share)| {
+			[
+				Span::styled(layer.symbol, layer.style),
+				Span::styled(format!(" {} {:.0}%  ", name, share), theme.text),
+			]
+		})
+		.collect();
+	legend.push(Span::styled(
+		format!(
+			"Idle {:.0}%  Ctxt {:.0}/s  Intr {:.0}/s",
+			latest.idle, app.context_switches, app.interrupts
+		),
+		theme.text,
+	));
+	let legend_width: usize = legend.iter().map(Span::width).sum();
+	let legend_height = legend_width.div_ceil(inner.width.max(1) as usize) as u16;
+
+	// Every core gets a line, as long as the graph keeps at least half of the space.
+	let graph_height = inner.height.saturating_sub(legend_height);
+	let cores_height = (app.core_times.len() as u16).min(graph_height / 2);
+
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints([
+			Constraint::Length(legend_height),
+			Constraint::Min(0),
+			Constraint::Length(cores_height),
+		])
+		.split(inner);
+
+	f.render_widget(
+		Paragraph::new(Line::from(legend)).wrap(Wrap { trim: true }),
+		chunks[0],
+	);
+
+	let graph = Layout::default()
+		.direction(Direction::Horizontal)
+		.constraints([Constraint::Length(5), Constraint::Min(0)])
+		.split(chunks[1]);
+	let mut labels = vec![Line::from("100%")];
+	if graph[0].height > 1 {
+		labels.resize(graph[0].height as usize - 1, Line::default());
+		labels.push(Line::from("0%"));
+	}
+	f.render_widget(Paragraph::new(labels).style(theme.text), graph[0]);
+	f.render_widget(
+		StackedGraph {
+			data: app
+				.cpu_times
+				.iter()
+				.map(|times| stacked(times).iter().map(|&share| share as f64).collect())
+				.collect(),
+			layers: &layers,
+			max: 100.0,
+			history: app.config.history.cpu_times,
+		},
+		graph[1],
+	);
+
+	let label_width = format!("Cpu{}", app.core_times.len().saturating_sub(1)).len() + 1;
+	let bar_width = (chunks[2].width as usize).saturating_sub(label_width);
+	let lines: Vec<Line> = app
+		.core_times
+		.iter()
+		.enumerate()
+		.map(|(core, times)| {
+			let mut spans = vec![Span::styled(
+				format!("{:<width$}", format!("Cpu{}", core), width = label_width),
+				theme.text,
+			)];
+			let mut start = 0.0;
+			let mut filled = 0;
+			for (layer, share) in layers.iter().zip(stacked(times)) {
+				start += share;
+				let end = (start / 100.0 * bar_width as f32).round() as usize;
+				let cells = end.min(bar_width).saturating_sub(filled);
+				spans.push(Span::styled(layer.symbol.repeat(cells), layer.style));
+				filled += cells;
+			}
+			Line::from(spans)
+		})
+		.collect();
+	f.render_widget(Paragraph::new(lines), chunks[2]);
+}
+
+/// The shares of `times` in the order they are stacked, leaving out the idle time.
+fn stacked(times: &CpuTimes) -> [f32; 8] {
+	[
+		times.user,
+		times.nice,
+		times.guest,
+		times.system,
+		times.irq,
+		times.softirq,
+		times.steal,
+		times.iowait,
+	]
+}
+
+/// The character of the heatmap for `usage`.
+fn shade(usage: f32) -> char {
+	let index = (usage / 100.0 * (SHADES.len() - 1) as f32).ceil() as usize;
+	SHADES[index.min(SHADES.len() - 1)]
+}
+
+/// Colors low usage like the graphs, medium usage as a warning and high usage like errors.
+fn usage_style(usage: f32) -> Style {
+	let theme = theme::current();
+	match usage {
+		usage if usage >= 80.0 => theme.graph_2,
+		usage if usage >= 50.0 => theme.graph_3,
+		_ => theme.graph_1,
+	}
+}
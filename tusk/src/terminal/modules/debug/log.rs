@@ -1,11 +1,9 @@
-use std::sync::Arc;
-
 use ratatui::{prelude::*, widgets::*};
 
-use crate::{config::theme::THEME, terminal::App};
+use crate::{config::theme, terminal::App};
 
 pub fn draw_log<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-	let theme = Arc::clone(&THEME);
+	let theme = theme::current();
 
 	let header_cells = ["Caller", "Message"]
 		.iter()
@@ -1,24 +1,14 @@
-use std::{collections::VecDeque, sync::Arc, time::Duration};
+use std::{collections::VecDeque, time::Duration};
 
 use ratatui::{prelude::*, text::Span, widgets::*, Frame};
 
-use crate::{
-	config::theme::THEME,
-	datapoints::{NETWORK_DATAPOINTS, TICK_TIME},
-	terminal::App,
-};
+use crate::{config::theme, terminal::App};
 
-/// Draws two graphs of the different tick components.
-#[allow(clippy::too_many_arguments)]
-pub fn draw_ticks<B: Backend>(
-	f: &mut Frame<B>,
-	app: &App,
-	full_tick_area: Rect,
-	split_tick_area: Rect,
-) {
-	let theme = Arc::clone(&THEME);
+/// Draws the real and processing tick times.
+pub fn draw_ticks<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
 
-	let (min, max) = min_max(&app.real_tick);
+	let (min, max) = min_max(&app.real_tick, app.config.tick);
 
 	let working_data: Vec<(f64, f64)> = app
 		.working_tick
@@ -60,7 +50,7 @@ pub fn draw_ticks<B: Backend>(
 		.x_axis(
 			Axis::default()
 				.style(theme.axis)
-				.bounds([0.0, NETWORK_DATAPOINTS as f64]),
+				.bounds([0.0, app.config.history.debug_ticks as f64]),
 		)
 		.y_axis(
 			Axis::default()
@@ -73,7 +63,14 @@ pub fn draw_ticks<B: Backend>(
 				]),
 		);
 
-	f.render_widget(chart, full_tick_area);
+	f.render_widget(chart, area);
+}
+
+/// Draws the time spent refreshing, drawing and handling events each tick.
+pub fn draw_tick_parts<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+
+	let (min, max) = min_max(&app.real_tick, app.config.tick);
 
 	let refresh_data: Vec<(f64, f64)> = app
 		.refresh_tick
@@ -129,7 +126,7 @@ pub fn draw_ticks<B: Backend>(
 		.x_axis(
 			Axis::default()
 				.style(theme.axis)
-				.bounds([0.0, NETWORK_DATAPOINTS as f64]),
+				.bounds([0.0, app.config.history.debug_ticks as f64]),
 		)
 		.y_axis(
 			Axis::default()
@@ -142,11 +139,11 @@ pub fn draw_ticks<B: Backend>(
 				]),
 		);
 
-	f.render_widget(chart, split_tick_area);
+	f.render_widget(chart, area);
 }
 
-fn min_max(data: &VecDeque<Duration>) -> (f64, f64) {
-	let tick_time = TICK_TIME + Duration::from_millis(2);
+fn min_max(data: &VecDeque<Duration>, tick: Duration) -> (f64, f64) {
+	let tick_time = tick + Duration::from_millis(2);
 	let min = 0.0;
 	let max = data.iter().max().unwrap_or(&tick_time);
 	let max = if max > &tick_time { *max } else { tick_time };
@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+
+use memu::{constants::KILOBYTE, units::Byte};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+	config::theme,
+	terminal::{
+		app_util::{byte_unit, format_bytes},
+		App,
+	},
+};
+
+/// Draws the bytes read and written per second by all block devices.
+pub fn draw_disk_io<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+
+	// Scales to at least a kilobyte, so an idle disk doesn't fill the graph with noise.
+	let max = app
+		.disk_read
+		.iter()
+		.chain(&app.disk_written)
+		.map(|bytes| bytes.as_f64())
+		.fold(KILOBYTE as f64, f64::max);
+	let (size, unit) = byte_unit(max);
+
+	let points = |data: &VecDeque<Byte>| -> Vec<(f64, f64)> {
+		data.iter()
+			.enumerate()
+			.map(|(i, bytes)| (i as f64, bytes.as_f64() / size))
+			.collect()
+	};
+	let read = points(&app.disk_read);
+	let written = points(&app.disk_written);
+
+	let datasets = vec![
+		Dataset::default()
+			.marker(theme.graph_style)
+			.graph_type(GraphType::Line)
+			.style(theme.graph_1)
+			.data(&read),
+		Dataset::default()
+			.marker(theme.graph_style)
+			.graph_type(GraphType::Line)
+			.style(theme.graph_2)
+			.data(&written),
+	];
+
+	let max = max / size;
+	// The legend of the chart is hidden on small areas, so the title names the graphs instead.
+	let title = Line::from(vec![
+		Span::raw(format!("Disk I/O ({}/s) ", unit)).bold(),
+		Span::styled("Read", theme.graph_1).bold(),
+		" / ".bold(),
+		Span::styled("Written", theme.graph_2).bold(),
+	]);
+	let chart = Chart::new(datasets)
+		.block(
+			Block::default()
+				.title(title)
+				.borders(Borders::ALL)
+				.border_style(theme.window),
+		)
+		.x_axis(
+			Axis::default()
+				.style(theme.axis)
+				.bounds([0.0, app.config.history.disk as f64]),
+		)
+		.y_axis(
+			Axis::default()
+				.style(theme.axis)
+				.bounds([0.0, max])
+				.labels(vec![
+					Span::styled("0", theme.text),
+					Span::styled(format!("{:.1}", max / 2.0), theme.text),
+					Span::styled(format!("{:.1}", max), theme.text),
+				]),
+		);
+
+	f.render_widget(chart, area);
+}
+
+/// Draws the throughput, operations, queue depth and utilization of every block device.
+pub fn draw_devices<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	let header_cells = [
+		"Device",
+		"Read/s",
+		"Written/s",
+		"Reads/s",
+		"Writes/s",
+		"Queue",
+		"Util",
+	]
+	.iter()
+	.map(|h| Cell::from(*h).style(theme.header));
+	let header = Row::new(header_cells).style(theme.graph_1).height(1);
+
+	let rows = app.devices.iter().map(|device| {
+		Row::new([
+			device.name.clone(),
+			format_bytes(device.read),
+			format_bytes(device.written),
+			format!("{:.0}", device.read_iops),
+			format!("{:.0}", device.write_iops),
+			format!("{:.2}", device.queue),
+			format!("{:.0}%", device.utilization),
+		])
+		.style(theme.text)
+	});
+
+	let table = Table::new(rows)
+		.header(header)
+		.block(
+			Block::default()
+				.borders(Borders::ALL)
+				.title("Block Devices".bold())
+				.border_style(theme.window),
+		)
+		.widths(&[
+			Constraint::Length(12),
+			Constraint::Length(10),
+			Constraint::Length(10),
+			Constraint::Length(8),
+			Constraint::Length(8),
+			Constraint::Length(6),
+			Constraint::Length(5),
+		]);
+
+	f.render_widget(table, area);
+}
+
+/// Draws the size and usage of every mounted filesystem.
+pub fn draw_filesystems<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	let header_cells = ["Mount", "Device", "Type", "Used", "Size", "Use"]
+		.iter()
+		.map(|h| Cell::from(*h).style(theme.header));
+	let header = Row::new(header_cells).style(theme.graph_1).height(1);
+
+	let rows = app.filesystems.iter().map(|filesystem| {
+		let usage = if filesystem.total.as_f64() > 0.0 {
+			filesystem.used.as_f64() / filesystem.total.as_f64() * 100.0
+		} else {
+			0.0
+		};
+		let style = match usage {
+			usage if usage >= 90.0 => theme.error,
+			_ => theme.text,
+		};
+
+		Row::new([
+			filesystem.mount_point.display().to_string(),
+			filesystem.device.clone(),
+			filesystem.file_system.clone(),
+			format_bytes(filesystem.used),
+			format_bytes(filesystem.total),
+			format!("{:.0}%", usage),
+		])
+		.style(style)
+	});
+
+	let table = Table::new(rows)
+		.header(header)
+		.block(
+			Block::default()
+				.borders(Borders::ALL)
+				.title("Filesystems".bold())
+				.border_style(theme.window),
+		)
+		.widths(&[
+			Constraint::Length(24),
+			Constraint::Length(16),
+			Constraint::Length(8),
+			Constraint::Length(10),
+			Constraint::Length(10),
+			Constraint::Length(5),
+		]);
+
+	f.render_widget(table, area);
+}
@@ -1,18 +1,28 @@
-use std::{error::Error, fmt::Display, sync::Arc};
+use std::{error::Error, fmt::Display};
 
 use ratatui::{prelude::*, widgets::*};
 
-use crate::config::theme::THEME;
+use crate::config::{
+	self,
+	keys::{Action, Mode},
+	theme,
+};
 
+/// Reasons a module can't draw its data, shown in its place.
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum FrameError {
 	MissingTracked,
+	MissingSensors,
+	MissingPressure,
 }
 
 impl Display for FrameError {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		let s = match *self {
 			Self::MissingTracked => "no tracked process found",
+			Self::MissingSensors => "no temperature sensors found",
+			Self::MissingPressure => "no pressure stall information found",
 		};
 		write!(f, "{}", s)
 	}
@@ -21,13 +31,45 @@ impl Display for FrameError {
 impl Error for FrameError {}
 
 pub fn draw_error<B: Backend>(f: &mut Frame<B>, error: FrameError, area: Rect) {
-	let theme = Arc::clone(&THEME);
+	let theme = theme::current();
+	let config = config::current();
 	let text = match error {
-		FrameError::MissingTracked => vec![
+		FrameError::MissingTracked => {
+			let hint = match config
+				.keys
+				.keys(Mode::Normal, Action::StartTrackInput)
+				.next()
+			{
+				Some(keys) => format!("Please select a process by pressing `{}`.", keys),
+				None => String::from("Please select a process with `--pid` or `--name`."),
+			};
+
+			vec![
+				Line::from("Error".red().bold()),
+				Line::from(""),
+				Line::from("No process is being tracked."),
+				Line::from(hint),
+			]
+		},
+		FrameError::MissingSensors => {
+			let hint = if config.collectors.is_enabled("sensors") {
+				"The kernel exposes none in /sys/class/hwmon, as is common in virtual machines."
+			} else {
+				"Enable the sensors collector in the configuration to read them."
+			};
+
+			vec![
+				Line::from("Error".red().bold()),
+				Line::from(""),
+				Line::from("No temperature sensors found."),
+				Line::from(hint),
+			]
+		},
+		FrameError::MissingPressure => vec![
 			Line::from("Error".red().bold()),
 			Line::from(""),
-			Line::from("No process is being tracked."),
-			Line::from("Please select a process by pressing `i`."),
+			Line::from("The kernel exposes no pressure stall information in /proc/pressure."),
+			Line::from("It needs Linux 4.20 or newer built with CONFIG_PSI, some distributions also need psi=1 on the kernel command line."),
 		],
 	};
 
@@ -0,0 +1,168 @@
+use memu::units::{Byte, GigaByte};
+use ratatui::{prelude::*, widgets::*};
+
+use super::stacked::{Layer, StackedGraph};
+use crate::{config::theme, terminal::App};
+
+/// Draws the used, buffered and cached memory as a stacked graph, with the swap below it.
+pub fn draw_memory<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	let block = Block::default()
+		.title("Memory".bold())
+		.borders(Borders::ALL)
+		.border_style(theme.window);
+	let inner = block.inner(area);
+	f.render_widget(block, area);
+
+	let Some(latest) = app.memory.back().copied() else {
+		return;
+	};
+
+	let memory_layers = [
+		Layer {
+			symbol: "█",
+			style: theme.graph_2,
+		},
+		Layer {
+			symbol: "▓",
+			style: theme.graph_3,
+		},
+		Layer {
+			symbol: "▒",
+			style: theme.graph_1,
+		},
+	];
+	let swap_layers = [Layer {
+		symbol: "█",
+		style: theme.graph_2,
+	}];
+
+	let has_swap = latest.swap_total > Byte::new(0);
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints(if has_swap {
+			vec![
+				Constraint::Length(1),
+				Constraint::Ratio(3, 4),
+				Constraint::Length(1),
+				Constraint::Ratio(1, 4),
+			]
+		} else {
+			vec![
+				Constraint::Length(1),
+				Constraint::Min(0),
+				Constraint::Length(1),
+				Constraint::Length(0),
+			]
+		})
+		.split(inner);
+
+	let total = latest.total;
+	let legend = Line::from(vec![
+		Span::styled(memory_layers[0].symbol, memory_layers[0].style),
+		Span::styled(format!(" Used {}  ", label(latest.used, total)), theme.text),
+		Span::styled(memory_layers[1].symbol, memory_layers[1].style),
+		Span::styled(
+			format!(" Buffers {}  ", label(latest.buffers, total)),
+			theme.text,
+		),
+		Span::styled(memory_layers[2].symbol, memory_layers[2].style),
+		Span::styled(
+			format!(" Cached {}  ", label(latest.cached, total)),
+			theme.text,
+		),
+		Span::styled(
+			format!(
+				"Available {} of {}",
+				label(latest.available, total),
+				label(total, total)
+			),
+			theme.text,
+		),
+	]);
+	f.render_widget(Paragraph::new(legend), chunks[0]);
+
+	let memory: Vec<Vec<f64>> = app
+		.memory
+		.iter()
+		.map(|sample| {
+			[sample.used, sample.buffers, sample.cached]
+				.iter()
+				.map(|bytes| bytes.as_f64())
+				.collect()
+		})
+		.collect();
+	draw_graph(f, app, memory, &memory_layers, total, chunks[1]);
+
+	let swap_legend = if has_swap {
+		format!(
+			" Swap {} of {}",
+			label(latest.swap_used, latest.swap_total),
+			label(latest.swap_total, latest.swap_total)
+		)
+	} else {
+		String::from("No swap")
+	};
+	f.render_widget(
+		Paragraph::new(Line::from(vec![
+			Span::styled(
+				if has_swap { swap_layers[0].symbol } else { "" },
+				swap_layers[0].style,
+			),
+			Span::styled(swap_legend, theme.text),
+		])),
+		chunks[2],
+	);
+
+	if has_swap {
+		let swap: Vec<Vec<f64>> = app
+			.memory
+			.iter()
+			.map(|sample| vec![sample.swap_used.as_f64()])
+			.collect();
+		draw_graph(f, app, swap, &swap_layers, latest.swap_total, chunks[3]);
+	}
+}
+
+/// Draws a stacked graph from 0 to `max`, labelling both on the left.
+fn draw_graph<B: Backend>(
+	f: &mut Frame<B>,
+	app: &App,
+	data: Vec<Vec<f64>>,
+	layers: &[Layer],
+	max: Byte,
+	area: Rect,
+) {
+	let theme = theme::current();
+	let top = label(max, max);
+	let chunks = Layout::default()
+		.direction(Direction::Horizontal)
+		.constraints([Constraint::Length(top.len() as u16 + 1), Constraint::Min(0)])
+		.split(area);
+
+	let mut labels = vec![Line::from(top)];
+	if area.height > 1 {
+		labels.resize(area.height as usize - 1, Line::default());
+		labels.push(Line::from(label(Byte::new(0), max)));
+	}
+	f.render_widget(Paragraph::new(labels).style(theme.text), chunks[0]);
+
+	f.render_widget(
+		StackedGraph {
+			data,
+			layers,
+			max: max.as_f64(),
+			history: app.config.history.memory,
+		},
+		chunks[1],
+	);
+}
+
+/// Formats `bytes` in gigabytes, or in megabytes if `total` is below a gigabyte.
+fn label(bytes: Byte, total: Byte) -> String {
+	if total >= GigaByte::from_u8(1).as_byte() {
+		bytes.as_giga_byte().as_string_with_unit_and_precision(2)
+	} else {
+		bytes.as_mega_byte().as_string_with_unit_and_precision(0)
+	}
+}
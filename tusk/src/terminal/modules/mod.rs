@@ -1,7 +1,13 @@
+pub(crate) mod cpu_frequency;
 pub(crate) mod cpu_usage;
 pub(crate) mod debug;
+pub(crate) mod disk;
 pub(crate) mod error;
+pub(crate) mod memory;
 pub(crate) mod network;
+pub(crate) mod pressure;
 pub(crate) mod processes;
+pub(crate) mod sensors;
+pub(crate) mod stacked;
 pub(crate) mod stats;
 pub(crate) mod tracked;
@@ -1,127 +1,180 @@
-use std::{collections::VecDeque, sync::Arc};
+use std::collections::VecDeque;
 
-use memu::units::KiloByte;
+use memu::units::Byte;
 use ratatui::{prelude::*, text::Span, widgets::*, Frame};
 
 use crate::{
-	config::theme::THEME,
-	datapoints::{NETWORK_CUTOFF, NETWORK_DATAPOINTS, NETWORK_MAX, NETWORK_MIN},
-	terminal::App,
+	config::theme,
+	terminal::{
+		app_util::{format_rate, history_width, rate_unit, BARS},
+		App,
+	},
 };
 
-/// Draws two network graphs in the given area
-pub fn draw_network<B: Backend>(f: &mut Frame<B>, app: &App, area_in: Rect, area_out: Rect) {
-	let theme = Arc::clone(&THEME);
-	let (min, max) = min_max(&app.network_in, &app.network_out);
+/// Draws the incoming network graph.
+pub fn draw_network_in<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	draw_graph(f, app, "Network In", &app.network_in, theme.graph_2, area);
+}
+
+/// Draws the outgoing network graph.
+pub fn draw_network_out<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	draw_graph(f, app, "Network Out", &app.network_out, theme.graph_1, area);
+}
+
+/// Draws a network graph, both graphs share the same scale and unit.
+fn draw_graph<B: Backend>(
+	f: &mut Frame<B>,
+	app: &App,
+	title: &str,
+	data: &VecDeque<Byte>,
+	style: Style,
+	area: Rect,
+) {
+	let theme = theme::current();
+	let max = max(
+		&app.network_in,
+		&app.network_out,
+		app.config.network.min.as_f64(),
+	);
+	let (size, unit) = rate_unit(max, app.config.network.unit);
+	let max = max / size;
 
-	let in_data: Vec<(f64, f64)> = app
-		.network_in
+	let data: Vec<(f64, f64)> = data
 		.iter()
 		.enumerate()
-		.map(|(i, &d)| (i as f64, d.as_f64()))
+		.map(|(i, bytes)| (i as f64, bytes.as_f64() / size))
 		.collect();
+	let title = format!("{} ({})", title, unit);
 
-	let in_dataset = Dataset::default()
+	let dataset = Dataset::default()
 		.marker(theme.graph_style)
 		.graph_type(GraphType::Line)
-		.style(theme.graph_2)
-		.data(&in_data);
+		.style(style)
+		.data(&data);
 
-	let chart = Chart::new(vec![in_dataset])
+	let chart = Chart::new(vec![dataset])
 		.block(
 			Block::default()
-				.title("Network In (MB/s)".bold())
+				.title(title.bold())
 				.borders(Borders::ALL)
 				.border_style(theme.window),
 		)
 		.x_axis(
 			Axis::default()
 				.style(theme.axis)
-				.bounds([0.0, NETWORK_DATAPOINTS as f64]),
+				.bounds([0.0, app.config.history.network as f64]),
 		)
 		.y_axis(
 			Axis::default()
 				.style(theme.axis)
-				.bounds([min, max])
+				.bounds([0.0, max])
 				.labels(vec![
 					Span::styled("0", theme.text),
-					Span::styled(format!("{:.0}", (min + max) / 2.0), theme.text),
-					Span::styled(format!("{:.0}", max), theme.text),
+					Span::styled(format!("{:.1}", max / 2.0), theme.text),
+					Span::styled(format!("{:.1}", max), theme.text),
 				]),
 		);
 
-	f.render_widget(chart, area_in);
+	f.render_widget(chart, area);
+}
 
-	let out_data: Vec<(f64, f64)> = app
-		.network_out
-		.iter()
-		.enumerate()
-		.map(|(i, &d)| (i as f64, d.as_f64()))
-		.collect();
+/// Draws a table with the traffic, errors and drops of every interface.
+///
+/// The history shows the received and transmitted bytes together, scaled to the interface's peak.
+pub fn draw_interfaces<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+	let header_cells = [
+		"Interface",
+		"Rx/s",
+		"Tx/s",
+		"Pkt/s",
+		"Errors",
+		"Drops",
+		"History",
+	]
+	.iter()
+	.map(|h| Cell::from(*h).style(theme.header));
+	let header = Row::new(header_cells).style(theme.graph_1).height(1);
 
-	let out_dataset = Dataset::default()
-		.marker(theme.graph_style)
-		.graph_type(GraphType::Line)
-		.style(theme.graph_1)
-		.data(&out_data);
+	let widths = [10, 9, 9, 9, 7, 7];
+	let history_width = history_width(area, &widths);
+
+	let rows = app.interfaces.iter().map(|interface| {
+		let Some(latest) = interface.samples.back() else {
+			return Row::new([interface.name.clone()]);
+		};
+
+		let traffic: Vec<u64> = interface
+			.samples
+			.iter()
+			.skip(
+				interface
+					.samples
+					.len()
+					.saturating_sub(history_width as usize),
+			)
+			.map(|sample| (sample.received + sample.transmitted).as_u64())
+			.collect();
+		let peak = traffic.iter().copied().max().unwrap_or_default().max(1);
+		let graph: String = traffic
+			.iter()
+			.map(|&bytes| BARS[(bytes * (BARS.len() as u64 - 1) / peak) as usize])
+			.collect();
+
+		let errors = latest.errors_received + latest.errors_transmitted;
+		let dropped = latest.dropped_received + latest.dropped_transmitted;
+		let style = if errors > 0 || dropped > 0 {
+			theme.error
+		} else {
+			theme.text
+		};
+
+		Row::new([
+			Cell::from(interface.name.clone()),
+			Cell::from(format_rate(latest.received, app.config.network.unit)),
+			Cell::from(format_rate(latest.transmitted, app.config.network.unit)),
+			Cell::from(format!(
+				"{:.0}/{:.0}",
+				latest.packets_received, latest.packets_transmitted
+			)),
+			Cell::from(format!(
+				"{}/{}",
+				latest.errors_received, latest.errors_transmitted
+			)),
+			Cell::from(format!(
+				"{}/{}",
+				latest.dropped_received, latest.dropped_transmitted
+			)),
+			Cell::from(graph).style(theme.graph_1),
+		])
+		.style(style)
+	});
 
-	let chart = Chart::new(vec![out_dataset])
+	let constraints: Vec<Constraint> = widths
+		.iter()
+		.map(|&width| Constraint::Length(width))
+		.chain([Constraint::Length(history_width)])
+		.collect();
+	let table = Table::new(rows)
+		.header(header)
 		.block(
 			Block::default()
-				.title("Network Out (MB/s)".bold())
 				.borders(Borders::ALL)
+				.title("Interfaces".bold())
 				.border_style(theme.window),
 		)
-		.x_axis(
-			Axis::default()
-				.style(theme.axis)
-				.bounds([0.0, NETWORK_DATAPOINTS as f64]),
-		)
-		.y_axis(
-			Axis::default()
-				.style(theme.axis)
-				.bounds([min, max])
-				.labels(vec![
-					Span::styled("0", theme.text),
-					Span::styled(format!("{:.0}", (min + max) / 2.0), theme.text),
-					Span::styled(format!("{:.0}", max), theme.text),
-				]),
-		);
+		.widths(&constraints);
 
-	f.render_widget(chart, area_out);
+	f.render_widget(table, area);
 }
 
-fn min_max(data1: &VecDeque<KiloByte>, data2: &VecDeque<KiloByte>) -> (f64, f64) {
-	let min = 0.0;
-	let max1 = data1.iter().max().unwrap_or(&NETWORK_MIN);
-
-	let max2 = data2.iter().max().unwrap_or(&NETWORK_MIN);
-
-	let data_max = if max1 < max2 { max1 } else { max2 };
-	let max = {
-		match (
-			data_max > &NETWORK_MIN,
-			data_max > &NETWORK_CUTOFF,
-			data_max > &NETWORK_MAX,
-		) {
-			// The value is higher than all other values.
-			(true, true, true) => *data_max,
-			// The value is between the max and the cutoff.
-			(true, true, false) => NETWORK_MAX,
-			// The value is between the min an the cutoff.
-			(true, false, false) => *data_max,
-			// The value is below the min.
-			(false, false, false) => NETWORK_MIN,
-			_ => unreachable!(concat!(
-				"the equality ",
-				stringify!(NETWORK_MIN),
-				" < ",
-				stringify!(NETWORK_CUTOFF),
-				" < ",
-				stringify!(NETWORK_MAX),
-				" does not hold true"
-			)),
-		}
-	};
-	(min, max.as_f64())
+/// The highest traffic in either direction in bytes per second, at least `min`.
+fn max(received: &VecDeque<Byte>, transmitted: &VecDeque<Byte>, min: f64) -> f64 {
+	received
+		.iter()
+		.chain(transmitted)
+		.map(|bytes| bytes.as_f64())
+		.fold(min, f64::max)
 }
@@ -0,0 +1,171 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+	config::theme,
+	sampler::{Pressure, ResourcePressure, Stall},
+	terminal::{
+		modules::error::{draw_error, FrameError},
+		App,
+	},
+};
+
+/// The lowest top of the graphs in percent, so a little pressure doesn't fill them.
+const MIN_TOP: f64 = 10.0;
+
+/// The width of the labels in front of the averages.
+const LABEL_WIDTH: usize = 6;
+
+/// A resource the kernel reports pressure for.
+#[derive(Clone, Copy)]
+enum Resource {
+	Cpu,
+	Memory,
+	Io,
+}
+
+impl Resource {
+	fn name(self) -> &'static str {
+		match self {
+			Self::Cpu => "Cpu",
+			Self::Memory => "Memory",
+			Self::Io => "Io",
+		}
+	}
+
+	/// The pressure on this resource out of `pressure`.
+	fn of(self, pressure: &ResourcePressure) -> Option<Pressure> {
+		match self {
+			Self::Cpu => pressure.cpu,
+			Self::Memory => pressure.memory,
+			Self::Io => pressure.io,
+		}
+	}
+}
+
+/// Draws the pressure on the cpu, memory and io next to each other.
+///
+/// Each shows the averages over 10, 60 and 300 seconds, those of the tracked process' cgroup and
+/// a graph of the 10 second averages.
+pub fn draw_pressure<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let available = app.pressure.back().is_none_or(|pressure| {
+		pressure.cpu.is_some() || pressure.memory.is_some() || pressure.io.is_some()
+	});
+	if !available {
+		draw_error(f, FrameError::MissingPressure, area);
+		return;
+	}
+
+	let chunks = Layout::default()
+		.direction(Direction::Horizontal)
+		.constraints([Constraint::Ratio(1, 3); 3])
+		.split(area);
+
+	for (resource, chunk) in [Resource::Cpu, Resource::Memory, Resource::Io]
+		.into_iter()
+		.zip(chunks.iter())
+	{
+		draw_resource(f, app, resource, *chunk);
+	}
+}
+
+fn draw_resource<B: Backend>(f: &mut Frame<B>, app: &App, resource: Resource, area: Rect) {
+	let theme = theme::current();
+	let title = format!("{} Pressure", resource.name());
+	let block = Block::default()
+		.title(title.bold())
+		.borders(Borders::ALL)
+		.border_style(theme.window);
+	let inner = block.inner(area);
+	f.render_widget(block, area);
+
+	let latest = app
+		.pressure
+		.back()
+		.and_then(|pressure| resource.of(pressure))
+		.unwrap_or_default();
+	let mut text = vec![
+		Line::styled(
+			format!("{:<LABEL_WIDTH$}{:>7}{:>7}{:>7}", "", "10s", "60s", "300s"),
+			theme.header,
+		),
+		averages("Some", Some(latest.some), theme.graph_1),
+		averages("Full", latest.full, theme.graph_2),
+	];
+	if let Some(tracked) = &app.tracked_pressure {
+		let pressure = resource.of(&tracked.pressure).unwrap_or_default();
+		text.push(Line::styled(
+			format!("Cgroup {}", tracked.path),
+			theme.header,
+		));
+		text.push(averages("Some", Some(pressure.some), theme.text));
+		text.push(averages("Full", pressure.full, theme.text));
+	}
+
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints([Constraint::Length(text.len() as u16), Constraint::Min(0)])
+		.split(inner);
+	f.render_widget(Paragraph::new(text), chunks[0]);
+
+	let some: Vec<(f64, f64)> = app
+		.pressure
+		.iter()
+		.enumerate()
+		.filter_map(|(i, pressure)| Some((i as f64, resource.of(pressure)?.some.avg10 as f64)))
+		.collect();
+	let full: Vec<(f64, f64)> = app
+		.pressure
+		.iter()
+		.enumerate()
+		.filter_map(|(i, pressure)| Some((i as f64, resource.of(pressure)?.full?.avg10 as f64)))
+		.collect();
+	let top = some
+		.iter()
+		.chain(&full)
+		.map(|&(_, avg)| avg)
+		.fold(MIN_TOP, f64::max)
+		.ceil();
+
+	let datasets = vec![
+		Dataset::default()
+			.marker(theme.graph_style)
+			.graph_type(GraphType::Line)
+			.style(theme.graph_1)
+			.data(&some),
+		Dataset::default()
+			.marker(theme.graph_style)
+			.graph_type(GraphType::Line)
+			.style(theme.graph_2)
+			.data(&full),
+	];
+
+	let chart = Chart::new(datasets)
+		.x_axis(
+			Axis::default()
+				.style(theme.axis)
+				.bounds([0.0, app.config.history.pressure as f64]),
+		)
+		.y_axis(
+			Axis::default()
+				.style(theme.axis)
+				.bounds([0.0, top])
+				.labels(vec![
+					Span::styled("0%", theme.text),
+					Span::styled(format!("{:.0}%", top), theme.text),
+				]),
+		);
+
+	f.render_widget(chart, chunks[1]);
+}
+
+/// A line with the averages of `stall`, or `-` if it isn't known.
+fn averages(label: &str, stall: Option<Stall>, style: Style) -> Line<'static> {
+	let text = match stall {
+		Some(stall) => format!(
+			"{:<LABEL_WIDTH$}{:>7.2}{:>7.2}{:>7.2}",
+			label, stall.avg10, stall.avg60, stall.avg300
+		),
+		None => format!("{:<LABEL_WIDTH$}{:>7}{:>7}{:>7}", label, "-", "-", "-"),
+	};
+	Line::styled(text, style)
+}
@@ -1,49 +1,155 @@
-use std::sync::Arc;
-
 use ratatui::{prelude::*, widgets::*};
 
-use crate::{config::theme::THEME, terminal::App};
+use crate::{
+	config::{layout::SortColumn, theme},
+	terminal::App,
+};
 
-pub fn draw_processes<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-	let theme = Arc::clone(&THEME);
-	let header_cells = ["Pid", "Name", "Memory", "Cpu", "Time", "Written", "Read"]
-		.iter()
-		.map(|h| Cell::from(*h).style(theme.header));
+/// The height of a row including the space below it.
+const ROW_HEIGHT: u16 = 2;
+
+/// The widest the name column gets in the tree, deeper processes are cut off.
+const MAX_TREE_NAME_WIDTH: u16 = 60;
+
+/// Draws the process table, listing only processes matching the filter of the current tab and
+/// the search.
+///
+/// The selected process is highlighted and kept in view, with a scrollbar on the right border.
+/// In the tree, processes are nested under their parents and the memory and cpu usage of their
+/// whole subtree is shown next to their own.
+pub fn draw_processes<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+	let theme = theme::current();
+	let filter = app.current_tab().filter.clone();
+	let sort = app.process_sort;
+	let tree = app.process_tree;
+
+	let mut columns = vec![
+		("Pid", Some(SortColumn::Pid)),
+		("Name", Some(SortColumn::Name)),
+		("Memory", Some(SortColumn::Memory)),
+		("Cpu", Some(SortColumn::Cpu)),
+	];
+	if tree {
+		columns.extend([("Tree Mem", None), ("Tree Cpu", None)]);
+	}
+	columns.extend([
+		("Time", Some(SortColumn::Time)),
+		("Written", Some(SortColumn::Written)),
+		("Read", Some(SortColumn::Read)),
+	]);
+	let header_cells = columns.iter().map(|&(h, column)| {
+		// The column the processes are sorted by points in the direction of the order.
+		if column != Some(sort.column) {
+			Cell::from(h).style(theme.header)
+		} else if sort.descending {
+			Cell::from(format!("{} ▼", h)).style(theme.header.bold())
+		} else {
+			Cell::from(format!("{} ▲", h)).style(theme.header.bold())
+		}
+	});
 
 	let header = Row::new(header_cells)
 		.style(theme.graph_1)
 		.height(1)
 		.bottom_margin(1);
 
-	let rows = app.processes.iter().map(|i| {
-		let cells = [
-			format!("{}", i.pid),
-			i.name.to_string(),
-			i.memory.as_string_with_unit_and_precision(2),
-			format!("{:.2}%", i.cpu_usage),
-			format!("{}", i.time),
-			format!("{}", i.total_written),
-			format!("{}", i.total_read),
-		];
-		Row::new(cells).height(1).bottom_margin(1)
-	});
+	let tree_rows = if tree { app.tree_rows() } else { Vec::new() };
+	let processes = if tree {
+		tree_rows.iter().map(|row| row.process).collect()
+	} else {
+		app.visible_processes()
+	};
+	let selected = app.selected_index(&processes);
+	let selected_pid = selected.map(|index| processes[index].pid);
+	let rows: Vec<Row> = processes
+		.iter()
+		.enumerate()
+		.map(|(index, i)| {
+			let mut cells = vec![format!("{}", i.pid)];
+			match tree_rows.get(index) {
+				Some(row) => cells.extend([
+					format!("{}{}", row.prefix, i.name),
+					i.memory.as_string_with_unit_and_precision(2),
+					format!("{:.2}%", i.cpu_usage),
+					row.memory.as_string_with_unit_and_precision(2),
+					format!("{:.2}%", row.cpu_usage),
+				]),
+				None => cells.extend([
+					i.name.to_string(),
+					i.memory.as_string_with_unit_and_precision(2),
+					format!("{:.2}%", i.cpu_usage),
+				]),
+			}
+			cells.extend([
+				format!("{}", i.time),
+				format!("{}", i.total_written),
+				format!("{}", i.total_read),
+			]);
+			Row::new(cells).height(1).bottom_margin(1)
+		})
+		.collect();
+	let count = rows.len();
+	let name_width = if tree {
+		tree_rows
+			.iter()
+			.map(|row| (row.prefix.chars().count() + row.process.name.chars().count()) as u16)
+			.max()
+			.unwrap_or_default()
+			.clamp(20, MAX_TREE_NAME_WIDTH)
+	} else {
+		20
+	};
+
+	app.select_process(selected_pid, selected);
+	// Borders and the header take up space as well.
+	app.process_page = (area.height.saturating_sub(2 + ROW_HEIGHT) / ROW_HEIGHT).max(1) as usize;
+
+	let title = match (&filter, &app.search) {
+		(Some(filter), Some(search)) => {
+			format!("Processes matching `{}` and `{}`", filter, search)
+		},
+		(Some(filter), None) | (None, Some(filter)) => {
+			format!("Processes matching `{}`", filter)
+		},
+		(None, None) => String::from("Processes"),
+	};
+	let title = if tree {
+		format!("{} as tree", title)
+	} else {
+		title
+	};
+
+	let mut widths = vec![Constraint::Max(6), Constraint::Max(name_width)];
+	widths.resize(columns.len(), Constraint::Max(10));
 
 	let table = Table::new(rows)
 		.header(header)
 		.block(
 			Block::default()
 				.borders(Borders::ALL)
-				.title("Processes".bold())
+				.title(title.bold())
 				.style(theme.window),
 		)
-		.widths(&[
-			Constraint::Max(6),
-			Constraint::Max(20),
-			Constraint::Max(10),
-			Constraint::Max(10),
-			Constraint::Max(10),
-			Constraint::Max(10),
-		]);
-
-	f.render_widget(table, area);
+		.highlight_style(theme.selected_text)
+		.widths(&widths);
+
+	f.render_stateful_widget(table, area, &mut app.process_table);
+
+	app.programs_scroll_state = app
+		.programs_scroll_state
+		.content_length(count as u16)
+		.viewport_content_length(app.process_page as u16)
+		.position(selected.unwrap_or_default() as u16);
+	f.render_stateful_widget(
+		Scrollbar::default()
+			.orientation(ScrollbarOrientation::VerticalRight)
+			.begin_symbol(None)
+			.end_symbol(None)
+			.style(theme.window),
+		area.inner(&Margin {
+			vertical: 1,
+			horizontal: 0,
+		}),
+		&mut app.programs_scroll_state,
+	);
 }
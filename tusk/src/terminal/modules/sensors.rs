@@ -0,0 +1,126 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{
+	config::theme,
+	terminal::{
+		app_util::{history_width, BARS},
+		modules::error::{draw_error, FrameError},
+		App,
+	},
+};
+
+/// The critical temperature assumed for sensors that don't report one.
+const DEFAULT_CRITICAL: f32 = 90.0;
+
+/// The share of the critical temperature from which a sensor is shown as a warning.
+const WARNING_SHARE: f32 = 0.85;
+
+/// The widest the sensor column gets, longer labels are cut off.
+const MAX_LABEL_WIDTH: u16 = 32;
+
+/// The width of the current, max and critical columns.
+const TEMPERATURE_WIDTH: u16 = 9;
+
+/// Draws a table with the current, highest and critical temperature of every sensor and a small
+/// graph of its history.
+///
+/// Sensors approaching their critical temperature are colored as a warning, sensors reaching it
+/// as an error.
+pub fn draw_sensors<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	if app.sensors.is_empty() {
+		draw_error(f, FrameError::MissingSensors, area);
+		return;
+	}
+
+	let theme = theme::current();
+	let header_cells = ["Sensor", "Current", "Max", "Critical", "History"]
+		.iter()
+		.map(|h| Cell::from(*h).style(theme.header));
+	let header = Row::new(header_cells).style(theme.graph_1).height(1);
+
+	let label_width = app
+		.sensors
+		.iter()
+		.map(|sensor| sensor.label.chars().count() as u16)
+		.max()
+		.unwrap_or_default()
+		.clamp(6, MAX_LABEL_WIDTH);
+	let widths = [
+		label_width,
+		TEMPERATURE_WIDTH,
+		TEMPERATURE_WIDTH,
+		TEMPERATURE_WIDTH,
+	];
+	let history_width = history_width(area, &widths);
+
+	let rows = app.sensors.iter().map(|sensor| {
+		let Some(latest) = sensor.samples.back() else {
+			return Row::new([sensor.label.clone()]);
+		};
+		let critical = latest.critical.unwrap_or(DEFAULT_CRITICAL);
+
+		let graph: Vec<Span> = sensor
+			.samples
+			.iter()
+			.skip(sensor.samples.len().saturating_sub(history_width as usize))
+			.map(|sample| {
+				let share = (sample.temperature / critical).clamp(0.0, 1.0);
+				let index = (share * (BARS.len() - 1) as f32).round() as usize;
+				Span::styled(
+					BARS[index].to_string(),
+					temperature_style(sample.temperature, critical, theme.graph_1),
+				)
+			})
+			.collect();
+
+		Row::new([
+			Cell::from(sensor.label.clone()),
+			Cell::from(format_temperature(latest.temperature)),
+			Cell::from(format_temperature(sensor.max)),
+			Cell::from(
+				latest
+					.critical
+					.map_or_else(|| String::from("-"), format_temperature),
+			),
+			Cell::from(Line::from(graph)),
+		])
+		.style(temperature_style(latest.temperature, critical, theme.text))
+	});
+
+	let constraints: Vec<Constraint> = widths
+		.iter()
+		.map(|&width| Constraint::Length(width))
+		.chain([Constraint::Length(history_width)])
+		.collect();
+	let table = Table::new(rows)
+		.header(header)
+		.block(
+			Block::default()
+				.borders(Borders::ALL)
+				.title("Sensors".bold())
+				.border_style(theme.window),
+		)
+		.widths(&constraints);
+
+	f.render_widget(table, area);
+}
+
+/// Formats a temperature like `45.0°C`, or `-` if it couldn't be read.
+fn format_temperature(temperature: f32) -> String {
+	if temperature.is_nan() {
+		String::from("-")
+	} else {
+		format!("{:.1}°C", temperature)
+	}
+}
+
+/// Colors temperatures reaching `critical` as an error and those approaching it as a warning,
+/// all others with `normal`.
+fn temperature_style(temperature: f32, critical: f32, normal: Style) -> Style {
+	let theme = theme::current();
+	match temperature {
+		temperature if temperature >= critical => theme.error,
+		temperature if temperature >= critical * WARNING_SHARE => theme.graph_3,
+		_ => normal,
+	}
+}
@@ -0,0 +1,49 @@
+use ratatui::{buffer::Buffer, prelude::*, widgets::*};
+
+/// A layer of a [`StackedGraph`], drawn with its own symbol so layers can be told apart without
+/// colors.
+pub struct Layer {
+	pub symbol: &'static str,
+	pub style: Style,
+}
+
+/// An area graph with the layers of every datapoint stacked on top of each other.
+///
+/// Like the charts, the datapoints are spread over the width according to the history length.
+pub struct StackedGraph<'a> {
+	/// The values of every layer, for every datapoint.
+	pub data: Vec<Vec<f64>>,
+	pub layers: &'a [Layer],
+	/// The value at the top of the graph.
+	pub max: f64,
+	pub history: usize,
+}
+
+impl Widget for StackedGraph<'_> {
+	fn render(self, area: Rect, buf: &mut Buffer) {
+		if area.area() == 0 || self.max <= 0.0 {
+			return;
+		}
+
+		let height = area.height as f64;
+		for column in 0..area.width {
+			let index = column as usize * self.history / area.width as usize;
+			let Some(values) = self.data.get(index) else {
+				break;
+			};
+
+			let mut bottom = 0.0;
+			for (value, layer) in values.iter().zip(self.layers) {
+				let top = bottom + value;
+				let from = (bottom / self.max * height).round() as u16;
+				let to = ((top / self.max * height).round() as u16).min(area.height);
+				for row in from..to {
+					buf.get_mut(area.x + column, area.bottom() - 1 - row)
+						.set_symbol(layer.symbol)
+						.set_style(layer.style);
+				}
+				bottom = top;
+			}
+		}
+	}
+}
@@ -1,20 +1,105 @@
-use std::sync::Arc;
-
 use ratatui::{prelude::*, widgets::*, Frame};
 
-use crate::{config::theme::THEME, terminal::App};
+use crate::{
+	config::theme,
+	terminal::{
+		app_util::{format_date, format_uptime},
+		App,
+	},
+};
 
+/// Draws a summary of the system, with the history of the 1 minute load average below it.
 pub fn draw_stats<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-	let theme = Arc::clone(&THEME);
-	let text = vec![
-		Line::from(format!("Name {}", app.cpu_name)),
-		Line::from(format!("Frequency {}MHz", app.cpu_frequency)),
-	];
-
-	let paragraph = Paragraph::new(text.clone()).style(theme.text).block(
-		Block::default()
-			.borders(Borders::ALL)
-			.border_style(theme.window),
+	let theme = theme::current();
+	let cores = app.cpu_cores.len().max(1) as f64;
+	let mut text = Vec::new();
+
+	if let Some(system) = &app.system {
+		let unknown = || String::from("unknown");
+		text.push(Line::from(vec![
+			Span::styled(
+				system.host_name.clone().unwrap_or_else(unknown),
+				theme.header,
+			),
+			Span::raw(format!(" {}", system.os.clone().unwrap_or_else(unknown))),
+		]));
+		text.push(Line::from(format!(
+			"Kernel {}",
+			system.kernel.clone().unwrap_or_else(unknown)
+		)));
+	}
+
+	text.push(Line::from(format!(
+		"Cpu {} {}MHz",
+		app.cpu_name, app.cpu_frequency
+	)));
+
+	if let Some(system) = &app.system {
+		// A load above the number of cores means processes are waiting for the cpu.
+		let load_style = |load: f64| {
+			if load > cores {
+				theme.error
+			} else {
+				theme.text
+			}
+		};
+
+		text.push(Line::from(format!(
+			"Up {} since {}",
+			format_uptime(system.uptime),
+			format_date(system.boot_time)
+		)));
+		text.push(Line::from(vec![
+			Span::raw("Load "),
+			Span::styled(
+				format!("{:.2}", system.load.one),
+				load_style(system.load.one),
+			),
+			Span::raw(" "),
+			Span::styled(
+				format!("{:.2}", system.load.five),
+				load_style(system.load.five),
+			),
+			Span::raw(" "),
+			Span::styled(
+				format!("{:.2}", system.load.fifteen),
+				load_style(system.load.fifteen),
+			),
+		]));
+		text.push(Line::from(format!(
+			"Users {} Processes {} Threads {}",
+			system.users, system.processes, system.threads
+		)));
+	}
+
+	let block = Block::default()
+		.title("System".bold())
+		.borders(Borders::ALL)
+		.border_style(theme.window);
+	let inner = block.inner(area);
+	f.render_widget(block, area);
+
+	let chunks = Layout::default()
+		.direction(Direction::Vertical)
+		.constraints([Constraint::Length(text.len() as u16), Constraint::Min(0)].as_ref())
+		.split(inner);
+
+	f.render_widget(Paragraph::new(text).style(theme.text), chunks[0]);
+
+	// Scaled to the number of cores, or the highest load if it was above that.
+	let max = app.load.iter().map(|load| load.one).fold(cores, f64::max);
+	let data: Vec<u64> = app
+		.load
+		.iter()
+		.skip(app.load.len().saturating_sub(chunks[1].width as usize))
+		.map(|load| (load.one / max * 100.0).round() as u64)
+		.collect();
+
+	f.render_widget(
+		Sparkline::default()
+			.style(theme.graph_1)
+			.max(100)
+			.data(&data),
+		chunks[1],
 	);
-	f.render_widget(paragraph, area);
 }
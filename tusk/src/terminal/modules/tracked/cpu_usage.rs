@@ -1,15 +1,13 @@
-use std::sync::Arc;
-
 use ratatui::{
 	prelude::*,
 	widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
 	Frame,
 };
 
-use crate::{config::theme::THEME, datapoints::CPU_USAGE_DATAPOINTS, terminal::App};
+use crate::{config::theme, terminal::App};
 
 pub fn draw_tracked_usage<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-	let theme = Arc::clone(&THEME);
+	let theme = theme::current();
 
 	let data: Vec<(f64, f64)> = app
 		.tracked
@@ -37,7 +35,7 @@ pub fn draw_tracked_usage<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
 		.x_axis(
 			Axis::default()
 				.style(theme.axis)
-				.bounds([0.0, CPU_USAGE_DATAPOINTS as f64]),
+				.bounds([0.0, app.config.history.tracked_process as f64]),
 		)
 		.y_axis(
 			Axis::default()
@@ -1 +1,33 @@
+use ratatui::{prelude::*, widgets::*};
 
+use crate::{config::theme, terminal::App};
+
+/// Draws general information about the tracked process.
+pub fn draw_tracked_info<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+	let theme = theme::current();
+
+	let tracked = app.tracked.as_ref().unwrap();
+
+	let text = vec![
+		Line::from(format!("Pid {}", tracked.pid)),
+		Line::from(format!("Name {}", tracked.name)),
+		Line::from(format!("Status {}", tracked.status)),
+		Line::from(format!("Running for {}s", tracked.time)),
+		Line::from(format!(
+			"Written {}",
+			tracked.total_written.as_string_with_unit_and_precision(2)
+		)),
+		Line::from(format!(
+			"Read {}",
+			tracked.total_read.as_string_with_unit_and_precision(2)
+		)),
+	];
+
+	let paragraph = Paragraph::new(text).style(theme.text).block(
+		Block::default()
+			.title("Tracked Process".bold())
+			.borders(Borders::ALL)
+			.border_style(theme.window),
+	);
+	f.render_widget(paragraph, area);
+}
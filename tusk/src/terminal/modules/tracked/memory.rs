@@ -1,20 +1,16 @@
 use memu::units::MegaByte;
 use ratatui::{prelude::*, widgets::*};
 
-use std::{collections::VecDeque, sync::Arc};
+use std::collections::VecDeque;
 
-use crate::{
-	config::theme::THEME,
-	datapoints::{TRACKED_MINIMUM_HIGHEST_MEMORY, TRACKED_PROCESS_DATAPOINTS},
-	terminal::App,
-};
+use crate::{config::theme, terminal::App};
 
 pub fn draw_tracked_memory<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-	let theme = Arc::clone(&THEME);
+	let theme = theme::current();
 
 	let tracked = app.tracked.as_ref().unwrap();
 
-	let (min, mut max) = min_max(&tracked.memory);
+	let (min, mut max) = min_max(&tracked.memory, app.config.tracked_minimum_memory);
 	max += 2.0;
 
 	let data: Vec<(f64, f64)> = tracked
@@ -40,7 +36,7 @@ pub fn draw_tracked_memory<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect)
 		.x_axis(
 			Axis::default()
 				.style(theme.axis)
-				.bounds([0.0, TRACKED_PROCESS_DATAPOINTS as f64]),
+				.bounds([0.0, app.config.history.tracked_process as f64]),
 		)
 		.y_axis(
 			Axis::default()
@@ -55,13 +51,13 @@ pub fn draw_tracked_memory<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect)
 	f.render_widget(chart, area);
 }
 
-fn min_max(data: &VecDeque<MegaByte>) -> (f64, f64) {
+fn min_max(data: &VecDeque<MegaByte>, minimum_highest: MegaByte) -> (f64, f64) {
 	let min = 0.0;
-	let max = data.iter().max().unwrap_or(&TRACKED_MINIMUM_HIGHEST_MEMORY);
-	let max = if max > &TRACKED_MINIMUM_HIGHEST_MEMORY {
+	let max = data.iter().max().unwrap_or(&minimum_highest);
+	let max = if max > &minimum_highest {
 		*max
 	} else {
-		TRACKED_MINIMUM_HIGHEST_MEMORY
+		minimum_highest
 	};
 	(min, max.as_f64())
 }
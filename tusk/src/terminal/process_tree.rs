@@ -0,0 +1,279 @@
+use std::collections::{HashMap, HashSet};
+
+use memu::units::MegaByte;
+
+use super::Process;
+
+/// A process listed in the tree, with the lines connecting it to its parent.
+pub struct TreeRow<'a> {
+	pub process: &'a Process,
+	/// The lines in front of the name, ending in a marker whether the children are shown.
+	pub prefix: String,
+	/// The memory used by the process and all its descendants.
+	pub memory: MegaByte,
+	/// The cpu usage of the process and all its descendants.
+	pub cpu_usage: f32,
+}
+
+/// Nests `processes` under their parents, keeping their order among siblings.
+///
+/// Processes whose parent isn't in `processes` are roots, as is the first process met of a cycle
+/// of parents. The descendants of processes in `collapsed` aren't listed, but still count towards
+/// their totals.
+pub fn build<'a>(processes: &[&'a Process], collapsed: &HashSet<u32>) -> Vec<TreeRow<'a>> {
+	let pids: HashSet<u32> = processes.iter().map(|process| process.pid).collect();
+	let mut roots = Vec::new();
+	let mut children: HashMap<u32, Vec<&Process>> = HashMap::new();
+
+	for &process in processes {
+		match process
+			.parent
+			.filter(|parent| *parent != process.pid && pids.contains(parent))
+		{
+			Some(parent) => children.entry(parent).or_default().push(process),
+			None => roots.push(process),
+		}
+	}
+
+	let mut tree = Tree {
+		children,
+		collapsed,
+		visited: HashSet::with_capacity(processes.len()),
+		nodes: Vec::with_capacity(processes.len()),
+	};
+	for root in roots {
+		tree.visit(root);
+	}
+	// Only processes in a cycle of parents are left, they would be lost without a root.
+	for &process in processes {
+		if !tree.visited.contains(&process.pid) {
+			tree.visit(process);
+		}
+	}
+	tree.rows()
+}
+
+struct Tree<'a, 'c> {
+	children: HashMap<u32, Vec<&'a Process>>,
+	collapsed: &'c HashSet<u32>,
+	visited: HashSet<u32>,
+	/// Every visited process, each after its parent.
+	nodes: Vec<Node<'a>>,
+}
+
+struct Node<'a> {
+	process: &'a Process,
+	/// The index of the parent's node.
+	parent: Option<usize>,
+	/// The prefix of the row, if the process is shown.
+	prefix: Option<String>,
+	memory: MegaByte,
+	cpu_usage: f32,
+}
+
+/// A process waiting to be visited.
+struct Pending<'a> {
+	process: &'a Process,
+	parent: Option<usize>,
+	/// Drawn in front of the process.
+	connector: String,
+	/// Drawn in front of the lines of the process's children.
+	indent: String,
+	shown: bool,
+}
+
+impl<'a> Tree<'a, '_> {
+	/// Adds `root` and its descendants depth first, using a stack so deep trees can't overflow.
+	fn visit(&mut self, root: &'a Process) {
+		let mut stack = vec![Pending {
+			process: root,
+			parent: None,
+			connector: String::new(),
+			indent: String::new(),
+			shown: true,
+		}];
+
+		while let Some(pending) = stack.pop() {
+			let process = pending.process;
+			self.visited.insert(process.pid);
+			let children: Vec<_> = self
+				.children
+				.remove(&process.pid)
+				.unwrap_or_default()
+				.into_iter()
+				.filter(|child| !self.visited.contains(&child.pid))
+				.collect();
+			let collapsed = self.collapsed.contains(&process.pid);
+
+			let prefix = pending.shown.then(|| {
+				let marker = match (children.is_empty(), collapsed) {
+					(false, true) => "▸ ",
+					(false, false) => "▾ ",
+					(true, _) if pending.connector.is_empty() => "  ",
+					(true, _) => "─ ",
+				};
+				format!("{}{}", pending.connector, marker)
+			});
+			let index = self.nodes.len();
+			self.nodes.push(Node {
+				process,
+				parent: pending.parent,
+				prefix,
+				memory: process.memory,
+				cpu_usage: process.cpu_usage,
+			});
+
+			let count = children.len();
+			// Pushed backwards, so the first child is visited first.
+			for (i, child) in children.into_iter().enumerate().rev() {
+				let last = i + 1 == count;
+				stack.push(Pending {
+					process: child,
+					parent: Some(index),
+					connector: format!("{}{}", pending.indent, if last { "└─" } else { "├─" }),
+					indent: format!("{}{}", pending.indent, if last { "  " } else { "│ " }),
+					shown: pending.shown && !collapsed,
+				});
+			}
+		}
+	}
+
+	/// Sums the totals of every process and lists the shown ones.
+	fn rows(mut self) -> Vec<TreeRow<'a>> {
+		// Children come after their parents, so going backwards every total is complete before
+		// it is added to the parent's.
+		for index in (0..self.nodes.len()).rev() {
+			if let Some(parent) = self.nodes[index].parent {
+				let (memory, cpu_usage) = (self.nodes[index].memory, self.nodes[index].cpu_usage);
+				self.nodes[parent].memory += memory;
+				self.nodes[parent].cpu_usage += cpu_usage;
+			}
+		}
+
+		self.nodes
+			.into_iter()
+			.filter_map(|node| {
+				Some(TreeRow {
+					process: node.process,
+					prefix: node.prefix?,
+					memory: node.memory,
+					cpu_usage: node.cpu_usage,
+				})
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sysinfo::ProcessStatus;
+
+	use super::*;
+
+	fn process(pid: u32, parent: Option<u32>) -> Process {
+		Process {
+			pid,
+			parent,
+			name: format!("p{}", pid),
+			user: String::from("root"),
+			time: 0,
+			memory: MegaByte::from(pid as u64),
+			status: ProcessStatus::Run,
+			total_written: MegaByte::from(0u64),
+			total_read: MegaByte::from(0u64),
+			cpu_usage: 1.0,
+		}
+	}
+
+	/// Draws the rows as `prefix name memory cpu`.
+	fn draw(processes: &[Process], collapsed: &[u32]) -> Vec<String> {
+		let processes: Vec<&Process> = processes.iter().collect();
+		build(&processes, &collapsed.iter().copied().collect())
+			.iter()
+			.map(|row| {
+				format!(
+					"{}{} {} {}",
+					row.prefix,
+					row.process.name,
+					row.memory.as_u64(),
+					row.cpu_usage
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn prefixes_and_totals() {
+		let processes = [
+			process(1, None),
+			process(2, Some(1)),
+			process(3, Some(2)),
+			process(4, Some(1)),
+			process(5, Some(2)),
+			process(6, None),
+		];
+
+		assert_eq!(
+			draw(&processes, &[]),
+			[
+				"▾ p1 15 5",
+				"├─▾ p2 10 3",
+				"│ ├── p3 3 1",
+				"│ └── p5 5 1",
+				"└── p4 4 1",
+				"  p6 6 1",
+			]
+		);
+	}
+
+	#[test]
+	fn collapsed_processes_keep_their_totals() {
+		let processes = [
+			process(1, None),
+			process(2, Some(1)),
+			process(3, Some(2)),
+			process(4, Some(1)),
+		];
+
+		assert_eq!(
+			draw(&processes, &[2]),
+			["▾ p1 10 4", "├─▸ p2 5 2", "└── p4 4 1"]
+		);
+		assert_eq!(draw(&processes, &[1, 2]), ["▸ p1 10 4"]);
+		// Collapsing a process without children changes nothing.
+		assert_eq!(draw(&processes, &[4]), draw(&processes, &[]));
+	}
+
+	#[test]
+	fn orphans_and_cycles_become_roots() {
+		let processes = [
+			process(2, Some(1)),
+			process(3, Some(3)),
+			process(4, Some(5)),
+			process(5, Some(4)),
+			process(6, Some(5)),
+		];
+
+		assert_eq!(
+			draw(&processes, &[]),
+			[
+				"  p2 2 1",
+				"  p3 3 1",
+				"▾ p4 15 3",
+				"└─▾ p5 11 2",
+				"  └── p6 6 1"
+			]
+		);
+	}
+
+	#[test]
+	fn deep_trees() {
+		let processes: Vec<Process> = (1..=5_000).map(|pid| process(pid, Some(pid - 1))).collect();
+		let processes: Vec<&Process> = processes.iter().collect();
+
+		let rows = build(&processes, &HashSet::new());
+		assert_eq!(rows.len(), 5_000);
+		assert_eq!(rows[0].cpu_usage, 5_000.0);
+		assert_eq!(rows[4_999].prefix.chars().count(), 2 * 4_999 + 2);
+	}
+}
@@ -1,4 +0,0 @@
-pub(super) mod debug;
-pub(super) mod default;
-pub(super) mod processes;
-pub(super) mod tracked;